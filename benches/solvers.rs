@@ -0,0 +1,38 @@
+use std::env;
+use std::path::Path;
+
+use aoc_2020::bench::{self, DEFAULT_ITERATIONS};
+use aoc_2020::days::PUZZLES;
+
+// `cargo bench [filter]` times every day's parse and solve phases on its
+// checked-in input, printing one line per phase as `dayN/phase`.
+fn main() {
+    let filter = env::args().skip(1).find(|arg| !arg.starts_with("--"));
+    let inputs = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+
+    for puzzle in PUZZLES {
+        let name = format!("day{}", puzzle.day);
+        if filter
+            .as_ref()
+            .is_some_and(|filter| !name.contains(filter.as_str()))
+        {
+            continue;
+        }
+
+        match bench::bench_puzzle(puzzle, &inputs, DEFAULT_ITERATIONS) {
+            Ok(timings) => {
+                for (phase, stats) in [
+                    ("parse", timings.parse),
+                    ("part1", timings.part1),
+                    ("part2", timings.part2),
+                ] {
+                    println!("{:<12} {}", format!("{}/{}", name, phase), stats);
+                }
+            }
+            Err(err) => {
+                eprintln!("{}: {}", name, err);
+                std::process::exit(1);
+            }
+        }
+    }
+}
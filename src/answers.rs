@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::toml::{parse_value, strip_comment};
+
+// Expected answers keyed by day and part, read from the small subset of TOML
+// used by `answers.toml`:
+//
+//     [day1]
+//     part1 = "299299"
+//     part2 = 287730716
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, u8), String>,
+}
+
+impl Answers {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?
+            .parse()
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.entries.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: &str) {
+        self.entries.insert((day, part), answer.to_string());
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn parse_number(s: &str, prefix: &str, line_no: usize) -> Result<u8, String> {
+    s.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("line {}: expected {}N, got {:?}", line_no, prefix, s))
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        let mut day = None;
+
+        for (idx, line) in s.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let table = table
+                    .strip_suffix(']')
+                    .ok_or_else(|| format!("line {}: unterminated table header", line_no))?;
+                day = Some(parse_number(table.trim(), "day", line_no)?);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `key = value`", line_no))?;
+            let day =
+                day.ok_or_else(|| format!("line {}: answer outside of a [dayN] table", line_no))?;
+            let part = parse_number(key.trim(), "part", line_no)?;
+            let value = parse_value(value.trim(), line_no)?;
+            if answers.entries.insert((day, part), value).is_some() {
+                return Err(format!(
+                    "line {}: duplicate answer for day {} part {}",
+                    line_no, day, part
+                ));
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let answers: Answers = "# comment
+[day1]
+part1 = \"299299\"  # trailing comment
+part2 = 287730716
+
+[ day7 ]
+part2 = \"a # b\"
+"
+        .parse()
+        .unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers.get(1, 1), Some("299299"));
+        assert_eq!(answers.get(1, 2), Some("287730716"));
+        assert_eq!(answers.get(7, 1), None);
+        assert_eq!(answers.get(7, 2), Some("a # b"));
+    }
+
+    #[test]
+    fn test_from_str_malformed() {
+        for input in [
+            "part1 = \"1\"",
+            "[day1\npart1 = \"1\"",
+            "[dayone]",
+            "[day1]\npart1 \"1\"",
+            "[day1]\npartone = \"1\"",
+            "[day1]\npart1 = \"1",
+            "[day1]\npart1 = one",
+            "[day1]\npart1 = 1\npart1 = 2",
+        ] {
+            assert!(input.parse::<Answers>().is_err(), "{:?}", input);
+        }
+    }
+}
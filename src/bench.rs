@@ -0,0 +1,173 @@
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::days::Puzzle;
+use crate::fetch;
+use crate::json_string;
+use crate::report::{json_ms, json_object};
+use crate::Solver;
+
+pub const DEFAULT_ITERATIONS: usize = 100;
+
+// Summary of repeated timings of one phase
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+        samples.sort();
+        // nearest rank, so a handful of samples still gives a real measurement
+        let rank = |percent: usize| samples[(samples.len() * percent).div_ceil(100).max(1) - 1];
+        Stats {
+            min: samples[0],
+            median: rank(50),
+            p95: rank(95),
+        }
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "min {:>9.3} ms  median {:>9.3} ms  p95 {:>9.3} ms",
+            ms(self.min),
+            ms(self.median),
+            ms(self.p95)
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Timings {
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl Timings {
+    // One JSON object per phase
+    pub fn to_json(&self, day: u8) -> Vec<String> {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+        .iter()
+        .map(|(phase, stats)| {
+            json_object(&[
+                ("day", day.to_string()),
+                ("phase", json_string(phase)),
+                ("iterations", self.iterations.to_string()),
+                ("min_ms", json_ms(stats.min)),
+                ("median_ms", json_ms(stats.median)),
+                ("p95_ms", json_ms(stats.p95)),
+            ])
+        })
+        .collect()
+    }
+}
+
+fn time<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    let samples = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+// Times parsing the input and each part separately, the parts always reuse
+// the same parsed input so only the solving is measured.
+pub fn measure<S: Solver>(input: &str, iterations: usize) -> Result<Timings, String> {
+    let parsed = S::parse(input)?;
+    S::part1(&parsed)?;
+    S::part2(&parsed)?;
+
+    Ok(Timings {
+        iterations,
+        parse: time(iterations, || S::parse(black_box(input))),
+        part1: time(iterations, || S::part1(black_box(&parsed))),
+        part2: time(iterations, || S::part2(black_box(&parsed))),
+    })
+}
+
+pub fn bench_puzzle(
+    puzzle: &Puzzle,
+    inputs_dir: &Path,
+    iterations: usize,
+) -> Result<Timings, String> {
+    let path = fetch::input_path(inputs_dir, puzzle.day);
+    let input = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    (puzzle.bench)(&input, iterations)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+
+        let single = Stats::from_samples(vec![Duration::from_millis(3)]);
+        assert_eq!(single.p95, Duration::from_millis(3));
+        assert_eq!(Stats::from_samples(vec![]), Stats::default());
+    }
+
+    struct Double;
+
+    impl Solver for Double {
+        const DAY: u8 = 0;
+        type Input = u32;
+
+        fn parse(input: &str) -> Result<u32, String> {
+            input.trim().parse().map_err(|e| format!("{:?}", e))
+        }
+
+        fn part1(n: &u32) -> Result<String, String> {
+            Ok(n.to_string())
+        }
+
+        fn part2(n: &u32) -> Result<String, String> {
+            n.checked_mul(2)
+                .map(|n| n.to_string())
+                .ok_or_else(|| "overflow".to_string())
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let timings = measure::<Double>("21", 5).unwrap();
+        assert_eq!(timings.iterations, 5);
+        assert!(timings.parse.min <= timings.parse.p95);
+        let json = timings.to_json(0);
+        assert_eq!(json.len(), 3);
+        assert!(json[1]
+            .starts_with("{\"day\": 0, \"phase\": \"part1\", \"iterations\": 5, \"min_ms\": "));
+
+        assert!(measure::<Double>("x", 5).is_err());
+        assert_eq!(measure::<Double>("4000000000", 5).unwrap_err(), "overflow");
+    }
+}
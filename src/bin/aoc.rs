@@ -0,0 +1,464 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_2020::answers::Answers;
+use aoc_2020::bench::{self, DEFAULT_ITERATIONS};
+use aoc_2020::config::{Config, DEFAULT_CONFIG};
+use aoc_2020::days::{self, Puzzle, PUZZLES};
+use aoc_2020::fetch::{self, Fetched};
+use aoc_2020::http::Client;
+use aoc_2020::json_string;
+use aoc_2020::report::{self, error_json, json_object, Format};
+use aoc_2020::scaffold;
+use aoc_2020::submit::{self, Ledger, Outcome, Submission, LEDGER};
+use aoc_2020::verify::{self, Status};
+use aoc_2020::watch::{self, Snapshot};
+
+const USAGE: &str = "\
+usage: aoc <command> [options] [--format human|json]
+
+With --format json every result is printed as one JSON object per line,
+errors as {\"error\": ...}.
+
+commands:
+  run --day N [--part P] [--inputs <dir>]
+        solve one or both parts of a day from <dir>/dayN.txt
+  run --all [--inputs <dir>] [--threads N]
+        solve every registered day at once on N threads (one per CPU by
+        default) and print a summary table
+  verify [--day N] [--inputs <dir>] [--answers <file>]
+        check every solver against its puzzle input and answers.toml
+  bench [--day N] [--inputs <dir>] [--iterations N]
+        time parsing and each part separately, reporting min/median/p95
+  fetch --day N [--inputs <dir>] [--config <file>] [--base-url <url>] [--force]
+        download a day's input unless it is already in <dir>, the session
+        token comes from AOC_SESSION or `session` in aoc.toml
+  submit --day N --part P [--inputs <dir>] [--config <file>] [--base-url <url>]
+         [--ledger <file>]
+        solve a part and post the answer, skipping answers the ledger
+        (.aoc/ledger.tsv by default) already knows are wrong
+  watch --day N [--inputs <dir>] [--root <dir>] [--interval-ms N]
+        re-run a day whenever its input or examples/dayN/ changes, polling
+        every N ms (500 by default), and show how each answer changed;
+        a relative <dir> is taken from --root like the examples are
+  new --day N [--root <dir>]
+        start a new day: src/days/dayN.rs from a template, its entry in
+        src/days/mod.rs, an empty inputs/dayN.txt and examples/dayN/";
+
+struct Options {
+    day: Option<u8>,
+    inputs: PathBuf,
+    answers: PathBuf,
+    iterations: usize,
+    config: PathBuf,
+    base_url: Option<String>,
+    force: bool,
+    part: Option<u8>,
+    ledger: Option<PathBuf>,
+    root: PathBuf,
+    format: Format,
+    all: bool,
+    threads: Option<usize>,
+    interval: Duration,
+}
+
+fn parse_options(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut args = args;
+    let mut options = Options {
+        day: None,
+        inputs: PathBuf::from("inputs"),
+        answers: PathBuf::from("answers.toml"),
+        iterations: DEFAULT_ITERATIONS,
+        config: PathBuf::from(DEFAULT_CONFIG),
+        base_url: None,
+        force: false,
+        part: None,
+        ledger: None,
+        root: PathBuf::from("."),
+        format: Format::Human,
+        all: false,
+        threads: None,
+        interval: Duration::from_millis(500),
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--day" => {
+                let day = value()?;
+                options.day = Some(day.parse().map_err(|_| format!("Invalid day {:?}", day))?)
+            }
+            "--inputs" => options.inputs = PathBuf::from(value()?),
+            "--answers" => options.answers = PathBuf::from(value()?),
+            "--iterations" => {
+                let iterations = value()?;
+                options.iterations = match iterations.parse() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid iteration count {:?}", iterations)),
+                }
+            }
+            "--config" => options.config = PathBuf::from(value()?),
+            "--base-url" => options.base_url = Some(value()?),
+            "--force" => options.force = true,
+            "--part" => {
+                let part = value()?;
+                options.part = match part.parse() {
+                    Ok(part @ 1..=2) => Some(part),
+                    _ => return Err(format!("Invalid part {:?}", part)),
+                }
+            }
+            "--ledger" => options.ledger = Some(PathBuf::from(value()?)),
+            "--root" => options.root = PathBuf::from(value()?),
+            "--format" => options.format = value()?.parse()?,
+            "--all" => options.all = true,
+            "--threads" => {
+                let threads = value()?;
+                options.threads = match threads.parse() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(format!("Invalid thread count {:?}", threads)),
+                }
+            }
+            "--interval-ms" => {
+                let interval = value()?;
+                options.interval = match interval.parse() {
+                    Ok(ms) if ms > 0 => Duration::from_millis(ms),
+                    _ => return Err(format!("Invalid interval {:?}", interval)),
+                }
+            }
+            _ => return Err(format!("Unknown option {:?}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+// The single requested day, or every registered one
+fn selected(day: Option<u8>) -> Result<&'static [Puzzle], String> {
+    match day {
+        Some(day) => days::find(day)
+            .map(std::slice::from_ref)
+            .ok_or_else(|| format!("No solver for day {}", day)),
+        None => Ok(PUZZLES),
+    }
+}
+
+fn run_all(options: Options) -> Result<bool, String> {
+    let threads = options.threads.unwrap_or_else(|| {
+        thread::available_parallelism()
+            .map(usize::from)
+            .unwrap_or(1)
+    });
+    let start = Instant::now();
+    let reports = report::run_all(PUZZLES, &options.inputs, threads);
+    let wall = start.elapsed();
+    let failed = reports.iter().filter(|r| r.answer.is_err()).count();
+
+    match options.format {
+        Format::Human => {
+            println!("{}", report::summary_table(&reports));
+            println!(
+                "{} failed, {:.3} ms wall clock on {} {}",
+                failed,
+                report::elapsed_ms(wall),
+                threads,
+                if threads == 1 { "thread" } else { "threads" }
+            );
+        }
+        Format::Json => {
+            for report in reports.iter() {
+                println!("{}", report.to_json());
+            }
+            let total = reports.iter().map(|r| r.elapsed).sum();
+            println!(
+                "{}",
+                json_object(&[
+                    ("total_ms", report::json_ms(total)),
+                    ("wall_ms", report::json_ms(wall)),
+                    ("failed", failed.to_string()),
+                ])
+            );
+        }
+    }
+    Ok(failed == 0)
+}
+
+fn run(options: Options) -> Result<bool, String> {
+    if options.all {
+        return run_all(options);
+    }
+    let day = options.day.ok_or("run needs --day N or --all")?;
+    let puzzle = days::find(day).ok_or_else(|| format!("No solver for day {}", day))?;
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let mut ok = true;
+    for part in parts {
+        let report = report::run(puzzle, part, &fetch::input_path(&options.inputs, day));
+        ok &= report.answer.is_ok();
+        println!("{}", report.format(options.format));
+    }
+    Ok(ok)
+}
+
+fn verify(options: Options) -> Result<bool, String> {
+    let answers = Answers::load(&options.answers)?;
+    let puzzles = selected(options.day)?;
+
+    let checks = verify::verify(puzzles, &options.inputs, &answers);
+    let count = |wanted: fn(&Status) -> bool| checks.iter().filter(|c| wanted(&c.status)).count();
+    let failed = count(|s| matches!(s, Status::Fail { .. }));
+    if options.format == Format::Json {
+        for check in checks.iter() {
+            println!("{}", check.to_json());
+        }
+        return Ok(failed == 0);
+    }
+
+    for check in checks.iter() {
+        println!("{}", check);
+    }
+    println!(
+        "{} passed, {} failed, {} missing in {:.3} ms",
+        count(|s| matches!(s, Status::Pass(_))),
+        failed,
+        count(|s| matches!(s, Status::Missing(_))),
+        checks
+            .iter()
+            .map(|c| c.elapsed.as_secs_f64() * 1000.0)
+            .sum::<f64>()
+    );
+
+    Ok(failed == 0)
+}
+
+fn bench(options: Options) -> Result<bool, String> {
+    let mut ok = true;
+    for puzzle in selected(options.day)? {
+        match bench::bench_puzzle(puzzle, &options.inputs, options.iterations) {
+            Ok(timings) if options.format == Format::Json => {
+                for line in timings.to_json(puzzle.day) {
+                    println!("{}", line);
+                }
+            }
+            Ok(timings) => {
+                println!("day {} ({} iterations)", puzzle.day, timings.iterations);
+                println!("  parse  {}", timings.parse);
+                println!("  part1  {}", timings.part1);
+                println!("  part2  {}", timings.part2);
+            }
+            Err(err) => {
+                match options.format {
+                    Format::Human => println!("day {}: {}", puzzle.day, err),
+                    Format::Json => println!(
+                        "{}",
+                        json_object(&[
+                            ("day", puzzle.day.to_string()),
+                            ("error", json_string(&err)),
+                        ])
+                    ),
+                }
+                ok = false;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+fn load_config(options: &Options) -> Result<Config, String> {
+    let mut config = Config::load(&options.config)?;
+    if let Some(base_url) = &options.base_url {
+        config.base_url = base_url.clone();
+    }
+    Ok(config)
+}
+
+fn fetch(options: Options) -> Result<bool, String> {
+    let day = options.day.ok_or("fetch needs --day N")?;
+    let config = load_config(&options)?;
+    let client = Client::new(&config);
+
+    let fetched = fetch::fetch_input(&client, day, &options.inputs, options.force)?;
+    match (options.format, &fetched) {
+        (Format::Human, Fetched::Cached(path)) => println!("{} already downloaded", path.display()),
+        (Format::Human, Fetched::Downloaded(path)) => {
+            println!("Saved day {} input to {}", day, path.display())
+        }
+        (Format::Json, Fetched::Cached(path)) | (Format::Json, Fetched::Downloaded(path)) => {
+            let downloaded = matches!(fetched, Fetched::Downloaded(_));
+            println!(
+                "{}",
+                json_object(&[
+                    ("day", day.to_string()),
+                    ("downloaded", downloaded.to_string()),
+                    ("input_path", json_string(&path.display().to_string())),
+                ])
+            )
+        }
+    }
+    Ok(true)
+}
+
+fn submit(options: Options) -> Result<bool, String> {
+    let day = options.day.ok_or("submit needs --day N")?;
+    let part = options.part.ok_or("submit needs --part P")?;
+    let puzzle = days::find(day).ok_or_else(|| format!("No solver for day {}", day))?;
+    let config = load_config(&options)?;
+    let mut ledger = Ledger::load(
+        options
+            .ledger
+            .clone()
+            .unwrap_or_else(|| config.state_dir.join(LEDGER)),
+    )?;
+
+    let path = fetch::input_path(&options.inputs, day);
+    let input = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let answer = (puzzle.part(part).unwrap())(&input)?;
+    if options.format == Format::Human {
+        println!("Day {} part {}: {}", day, part, answer);
+    }
+
+    let client = Client::new(&config);
+    let submission = submit::submit_answer(&client, &mut ledger, day, part, &answer)?;
+    let (submitted, outcome) = match &submission {
+        Submission::Skipped(outcome) => (false, outcome),
+        Submission::Submitted(outcome) => (true, outcome),
+    };
+    match options.format {
+        Format::Human if !submitted => {
+            println!("Not submitted, the ledger already has this as {}", outcome)
+        }
+        Format::Human => match outcome {
+            Outcome::RateLimited(Some(wait)) => println!("Rate limited, {} left to wait", wait),
+            Outcome::WrongLevel => {
+                println!(
+                    "Wrong level, part {} is still locked or already solved",
+                    part
+                )
+            }
+            outcome => println!("{}", outcome),
+        },
+        Format::Json => {
+            let mut fields = vec![
+                ("day", day.to_string()),
+                ("part", part.to_string()),
+                ("answer", json_string(&answer)),
+                ("submitted", submitted.to_string()),
+                ("outcome", json_string(&outcome.to_string())),
+            ];
+            if let Outcome::RateLimited(Some(wait)) = outcome {
+                fields.push(("wait", json_string(wait)));
+            }
+            fields.push(("input_path", json_string(&path.display().to_string())));
+            println!("{}", json_object(&fields));
+        }
+    }
+    Ok(matches!(outcome, Outcome::Correct | Outcome::AlreadySolved))
+}
+
+fn new_day(options: Options) -> Result<bool, String> {
+    let day = options.day.ok_or("new needs --day N")?;
+    for path in scaffold::new_day(&options.root, day)? {
+        match options.format {
+            Format::Human => println!("Wrote {}", path.display()),
+            Format::Json => println!(
+                "{}",
+                json_object(&[("wrote", json_string(&path.display().to_string()))])
+            ),
+        }
+    }
+    Ok(true)
+}
+
+// Never returns on its own, stop it with Ctrl-C
+fn watch(options: Options) -> Result<bool, String> {
+    let day = options.day.ok_or("watch needs --day N")?;
+    let puzzle = days::find(day).ok_or_else(|| format!("No solver for day {}", day))?;
+    // both the input and the examples live under --root, an absolute
+    // --inputs replaces it
+    let input_path = fetch::input_path(&options.root.join(&options.inputs), day);
+    if options.format == Format::Human {
+        println!(
+            "Watching {} and {}",
+            input_path.display(),
+            aoc_2020::examples::examples_dir(&options.root, day).display()
+        );
+    }
+
+    let mut snapshot = Snapshot::default();
+    let mut entries = vec![];
+    loop {
+        let latest = Snapshot::take(&options.root, &input_path, day);
+        let changed: Vec<String> = latest
+            .changed(&snapshot)
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        if !changed.is_empty() {
+            let previous = entries;
+            entries = watch::run_once(puzzle, &options.root, &input_path);
+            match options.format {
+                Format::Human => {
+                    // on the first run every file is new
+                    if !previous.is_empty() {
+                        println!("\nchanged: {}", changed.join(", "));
+                    }
+                    for entry in entries.iter() {
+                        println!(
+                            "  {}",
+                            entry.to_human(watch::previous(&previous, &entry.label))
+                        );
+                    }
+                }
+                Format::Json => {
+                    for entry in entries.iter() {
+                        println!(
+                            "{}",
+                            entry.to_json(watch::previous(&previous, &entry.label))
+                        );
+                    }
+                }
+            }
+            snapshot = latest;
+        }
+        thread::sleep(options.interval);
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    // known up front so that errors parsing the rest are reported in it too
+    let json = args
+        .windows(2)
+        .any(|pair| pair[0] == "--format" && pair[1] == "json");
+
+    let mut args = args.into_iter();
+    let result = match args.next().as_deref() {
+        Some("run") => parse_options(args).and_then(run),
+        Some("verify") => parse_options(args).and_then(verify),
+        Some("bench") => parse_options(args).and_then(bench),
+        Some("fetch") => parse_options(args).and_then(fetch),
+        Some("submit") => parse_options(args).and_then(submit),
+        Some("watch") => parse_options(args).and_then(watch),
+        Some("new") => parse_options(args).and_then(new_day),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) if json => {
+            println!("{}", error_json(&err));
+            process::exit(2);
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    }
+}
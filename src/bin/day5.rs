@@ -1,61 +1,109 @@
-use std::ops::BitXorAssign;
-use std::str::FromStr;
-
-#[derive(Debug, PartialEq, Default)]
-struct PlaneTicket {
-    id: u16,
-    row: u16,
-    seat: u16,
-}
+use std::fs;
+use std::process;
 
-impl BitXorAssign for PlaneTicket {
-    fn bitxor_assign(&mut self, rhs: Self) {
-        self.id ^= rhs.id;
-        self.row ^= rhs.row;
-        self.seat ^= rhs.seat;
-    }
+use aoc_2020::days::day5::{format_ranges, parse_tickets, Day5, SeatLayout, SeatMap};
+
+struct Config {
+    filename: String,
+    layout: SeatLayout,
+    show_map: bool,
+    svg: Option<String>,
 }
 
-impl FromStr for PlaneTicket {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let acc = s.chars().fold((0u16, 0u16), |a, c| match c {
-            'F' => (a.0 << 1, a.1),
-            'B' => ((a.0 ^ 1) << 1, a.1),
-            'L' => (a.0, a.1 << 1),
-            'R' => (a.0, (a.1 ^ 1) << 1),
-            _ => (a.0, a.1),
-        });
-
-        let (row, seat) = (acc.0 >> 1, acc.1 >> 1);
-        Ok(PlaneTicket {
-            id: row * 8 + seat,
-            row,
-            seat,
-        })
+// day5 <file> [rows seats] [--map] [--svg <out.svg>]
+fn parse_args(args: Vec<String>) -> Result<Config, String> {
+    let mut args = args.into_iter();
+    let mut positional: Vec<String> = vec![];
+    let mut show_map = false;
+    let mut svg = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--map" => show_map = true,
+            "--svg" => svg = args.next(),
+            _ => positional.push(arg),
+        }
     }
+
+    let number = |arg: &String| {
+        arg.parse::<u32>()
+            .map_err(|_| format!("Invalid plane dimension {:?}", arg))
+    };
+    let layout = match positional.as_slice() {
+        [] => return Err("Missing input file".to_string()),
+        [_] => SeatLayout::default(),
+        [_, rows, seats] => SeatLayout::with_dimensions(number(rows)?, number(seats)?)?,
+        [_, rows] => {
+            return Err(format!(
+                "Got {} rows but no seats, give both e.g. {} 8",
+                rows, rows
+            ))
+        }
+        [_, _, _, extra @ ..] => return Err(format!("Unexpected arguments {:?}", extra)),
+    };
+
+    Ok(Config {
+        filename: positional[0].clone(),
+        layout,
+        show_map,
+        svg,
+    })
 }
 
 fn main() -> std::io::Result<()> {
-    let filename = aoc_2020::parse_simple_args();
+    let args = aoc_2020::report::run_if_json::<Day5>();
+    let Config {
+        filename,
+        layout,
+        show_map,
+        svg,
+    } = match parse_args(args) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
     let mut max = 0;
-    let mut missing: PlaneTicket = Default::default();
+    let mut seat_map = SeatMap::new(layout.clone());
 
-    if let Ok(lines) = aoc_2020::read_lines(filename) {
-        for line in lines {
-            let ticket: PlaneTicket = line.unwrap().parse().unwrap();
-
-            if max < ticket.id {
-                max = ticket.id;
-            }
-
-            missing ^= ticket;
+    for ticket in parse_tickets(&fs::read_to_string(filename)?, &layout).unwrap() {
+        if max < ticket.id {
+            max = ticket.id;
         }
+
+        seat_map.insert(&ticket);
     }
 
+    let report = seat_map.report();
+
+    println!(
+        "Plane layout: {} rows x {} seats",
+        layout.rows(),
+        layout.seats()
+    );
     println!("Max ticket id: {}", max);
-    println!("Missing ticket: {:?}", missing);
+    println!("My seat: {}", format_ranges(&report.my_seat));
+    println!("Missing at front: {}", format_ranges(&report.missing_front));
+    println!("Missing at back: {}", format_ranges(&report.missing_back));
+    if !report.missing_other.is_empty() {
+        println!("Other missing: {}", format_ranges(&report.missing_other));
+    }
+    if !report.duplicates.is_empty() {
+        println!(
+            "Duplicate boarding passes: {}",
+            format_ranges(&report.duplicates)
+        );
+    }
+
+    if show_map {
+        print!("{}", seat_map.render_text(&report));
+    }
+
+    if let Some(path) = svg {
+        fs::write(&path, seat_map.render_svg(&report))?;
+        println!("Seat map written to {}", path);
+    }
 
     Ok(())
 }
@@ -64,28 +112,28 @@ fn main() -> std::io::Result<()> {
 mod test {
     use super::*;
 
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
     #[test]
-    fn test_from_str() {
-        let test_inputs = vec!["BFFFBBFRRR", "FFFBBBFRRR", "BBFFBBFRLL"];
-        let expected = vec![
-            PlaneTicket {
-                id: 567,
-                row: 70,
-                seat: 7,
-            },
-            PlaneTicket {
-                id: 119,
-                row: 14,
-                seat: 7,
-            },
-            PlaneTicket {
-                id: 820,
-                row: 102,
-                seat: 4,
-            },
-        ];
-        for (input, res) in test_inputs.iter().zip(expected) {
-            assert_eq!(input.parse::<PlaneTicket>().unwrap(), res);
-        }
+    fn test_parse_args() {
+        let config = parse_args(args(&["day5.txt", "256", "16", "--map"])).unwrap();
+        assert_eq!(config.layout.rows(), 256);
+        assert_eq!(config.layout.seats(), 16);
+        assert!(config.show_map);
+        assert_eq!(
+            parse_args(args(&["day5.txt"])).unwrap().layout,
+            SeatLayout::default()
+        );
+
+        assert_eq!(
+            parse_args(args(&["day5.txt", "256"])).err(),
+            Some("Got 256 rows but no seats, give both e.g. 256 8".to_string())
+        );
+        assert!(parse_args(args(&["day5.txt", "256", "x"])).is_err());
+        assert!(parse_args(args(&["day5.txt", "100", "8"])).is_err());
+        assert!(parse_args(args(&["day5.txt", "8", "8", "8"])).is_err());
+        assert!(parse_args(args(&[])).is_err());
     }
 }
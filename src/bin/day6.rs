@@ -1,70 +1,79 @@
-use std::collections::HashSet;
+use std::collections::BTreeMap;
+use std::process;
 
-fn check_anyone_yes(lines: aoc_2020::InputLines) -> u16 {
-    let mut char_set = HashSet::<char>::new();
-    let mut count = 0;
+use aoc_2020::days::day6::{
+    check_anyone_yes, check_everyone_yes, count_quorum, read_groups, Day6, Quorum,
+};
 
-    lines
-        .map(|line| match line {
-            Ok(line) if line != *"" => {
-                line.chars().map(|c| char_set.insert(c)).for_each(drop);
-            }
-            _ => {
-                count += char_set.len();
-                char_set = HashSet::<char>::new();
-            }
-        })
-        .for_each(drop);
-
-    count as u16
-}
-
-fn check_everyone_yes(lines: aoc_2020::InputLines) -> u16 {
-    let mut in_all_lines = None;
-    let mut count = 0;
+// day6 <file> [quorum] [--groups]
+// where quorum is `any`, `all`, `majority`, a count `k` or a percentage `p%`
+fn parse_args(args: Vec<String>) -> (String, Option<Quorum>, bool) {
+    let mut positional: Vec<String> = vec![];
+    let mut show_groups = false;
 
-    lines
-        .map(|line| match line {
-            Ok(line) if line != *"" => {
-                let mut char_set = HashSet::<char>::new();
-                line.chars().map(|c| char_set.insert(c)).for_each(drop);
-                in_all_lines = match &in_all_lines {
-                    Some(set) => Some(char_set.intersection(&set).copied().collect()),
-                    None => Some(char_set.iter().cloned().collect()),
-                };
-            }
-            _ => {
-                count += match &in_all_lines {
-                    Some(set) => set.len(),
-                    None => 0,
-                };
-
-                in_all_lines = None;
-            }
-        })
-        .for_each(drop);
+    for arg in args {
+        match arg.as_str() {
+            "--groups" => show_groups = true,
+            _ => positional.push(arg),
+        }
+    }
 
-    count as u16
+    let quorum = positional.get(1).map(|q| q.parse().unwrap());
+    (positional[0].clone(), quorum, show_groups)
 }
 
 fn main() -> std::io::Result<()> {
-    let filename = aoc_2020::parse_simple_args();
+    let args = aoc_2020::report::run_if_json::<Day6>();
+    let (filename, quorum, show_groups) = parse_args(args);
+    let groups = match read_groups(aoc_2020::read_lines(filename)?) {
+        Ok(groups) => groups,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
 
-    if let Ok(lines) = aoc_2020::read_lines(filename.clone()) {
-        let some_count = check_anyone_yes(lines);
-        println!(
-            "Total sum of counts where someone answewred yes: {}",
-            some_count
-        );
-    }
+    println!(
+        "Total sum of counts where someone answewred yes: {}",
+        check_anyone_yes(&groups)
+    );
+    println!(
+        "Total sum of counts where everyone answered yes: {}",
+        check_everyone_yes(&groups)
+    );
 
-    if let Ok(lines) = aoc_2020::read_lines(filename) {
-        let all_count = check_everyone_yes(lines);
+    if let Some(quorum) = quorum {
         println!(
-            "Total sum of counts where everyone answered yes: {}",
-            all_count
+            "Total sum of counts meeting quorum {:?}: {}",
+            quorum,
+            count_quorum(&groups, quorum)
         );
-    };
+
+        let mut per_question = BTreeMap::<char, usize>::new();
+        for group in groups.iter() {
+            for c in group.questions_meeting(quorum).chars() {
+                *per_question.entry(c).or_insert(0) += 1;
+            }
+        }
+        println!("Groups meeting quorum per question: {:?}", per_question);
+
+        if show_groups {
+            for (idx, group) in groups.iter().enumerate() {
+                let questions: String = group.questions_meeting(quorum).chars().collect();
+                let counts: BTreeMap<char, usize> = ('a'..='z')
+                    .zip(group.counts.iter().copied())
+                    .filter(|(_, count)| *count > 0)
+                    .collect();
+                println!(
+                    "Group {} ({} people): {:?} counts {:?}",
+                    idx + 1,
+                    group.size,
+                    questions,
+                    counts
+                );
+            }
+        }
+    }
 
     Ok(())
 }
@@ -1,127 +1,421 @@
-use core::str::FromStr;
-use std::collections::{HashMap, HashSet};
-use std::hash::{Hash, Hasher};
-
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
-struct Bag {
-    name: String,
-    parents: HashSet<String>,
-    children: HashSet<String>,
-}
+use std::fs;
+use std::process;
 
-impl FromStr for Bag {
-    type Err = String;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, bag) = s.split(' ').fold((vec![], Bag{..Default::default()}), |acc, w| {
-            let (mut buffer, mut bag) = acc;
-            match w {
-                "contain" => {
-                    bag.name = buffer.join(" ");
-                    buffer = vec![];
-                }
-                "bag," | "bags," | "bag." | "bags." => {
-                    bag.children.insert(buffer.join(" "));
-                    buffer = vec![];
-                }
-                "bag" | "bags" => {}
-                word if word.parse::<u8>().is_ok() => {}
-                word => buffer.push(word),
-            }
+use aoc_2020::days::day7::{BagId, BagIndex, Day7, Subgraph};
+use aoc_2020::Solver;
+
+const REPL_COMMANDS: [&str; 8] = [
+    "parents", "contains", "path", "paths", "depth", "stats", "help", "quit",
+];
 
-            (buffer, bag)
-        });
+const DEFAULT_PATH_LIMIT: usize = 10;
 
-        Ok(bag)
+// a -(2)-> b -(3)-> c
+fn format_chain(bags: &BagIndex, chain: &[BagId]) -> String {
+    let mut out = bags.name(chain[0]).to_string();
+    for pair in chain.windows(2) {
+        let count = bags.edge_count(pair[0], pair[1]).unwrap();
+        out.push_str(&format!(" -({})-> {}", count, bags.name(pair[1])));
     }
+    out
 }
 
-impl Hash for Bag {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.name.hash(state);
+// Everything known about how `from` can hold `to`: whether it can at all,
+// the shortest and heaviest chains, and up to `limit` simple paths.
+fn describe_paths(bags: &BagIndex, from: BagId, to: BagId, limit: usize) -> String {
+    if !bags.can_contain(from, to) {
+        return format!(
+            "{} bags cannot contain {} bags",
+            bags.name(from),
+            bags.name(to)
+        );
     }
+
+    let shortest = bags.shortest_chain(from, to).unwrap();
+    let (heaviest, total) = bags.heaviest_chain(from, to).unwrap();
+    let paths = bags.simple_paths(from, to, limit);
+
+    let mut lines = vec![
+        format!(
+            "{} bags can contain {} bags",
+            bags.name(from),
+            bags.name(to)
+        ),
+        format!("Shortest chain: {}", format_chain(bags, &shortest)),
+        format!(
+            "Heaviest chain ({} bags): {}",
+            total,
+            format_chain(bags, &heaviest)
+        ),
+        format!("Simple paths ({}, at most {}):", paths.len(), limit),
+    ];
+    lines.extend(
+        paths
+            .iter()
+            .map(|path| format!("  {}", format_chain(bags, path))),
+    );
+    lines.join("\n")
 }
 
-#[derive(Default)]
-struct BagIndex {
-    idx: HashMap::<String, Bag>,
+// "1 level", "4 levels"
+fn levels(depth: usize) -> String {
+    match depth {
+        1 => "1 level".to_string(),
+        n => format!("{} levels", n),
+    }
 }
 
-impl BagIndex {
-    fn new() -> Self {
-        BagIndex{..Default::default()}
-    }
-
-    fn update_parents(&mut self, bag: &Bag) {
-        for bag_name in bag.children.iter() {
-            match self.idx.get(bag_name) {
-                Some(old_bag) => {
-                    let mut new_bag = old_bag.clone();
-                    new_bag.parents.insert(bag.name.clone());
-                    self.idx.insert(bag_name.to_string(), new_bag);
-                },
-                None => {
-                    self.idx.insert(bag_name.to_string(), Bag{
-                        name: bag_name.to_string(),
-                        parents: [bag.name.clone()].iter().cloned().collect(), 
-                        ..Default::default()
-                    });
+const REPL_HELP: &str = "\
+parents <colour>             bags that can eventually contain <colour>
+contains <colour>            how many bags <colour> must hold
+path <colour> -> <colour>    shortest containment chain between two bags
+paths <colour> -> <colour> [limit]
+                             shortest, heaviest and all simple chains
+depth <colour>               how deeply bags are nested inside <colour>
+stats                        size of the rule set
+quit                         leave the shell";
+
+fn lookup(bags: &BagIndex, colour: &str) -> Result<BagId, String> {
+    bags.get(colour.trim())
+        .ok_or_else(|| format!("No rule mentions a {} bag", colour.trim()))
+}
+
+// Answers a single line typed into the query shell.
+fn run_command(bags: &BagIndex, line: &str) -> Result<String, String> {
+    let line = line.trim();
+    let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+
+    match command {
+        "parents" => {
+            let id = lookup(bags, rest)?;
+            let ancestors = bags.ancestors(id, None);
+            let names: Vec<&str> = ancestors
+                .order()
+                .into_iter()
+                .map(|id| bags.name(id))
+                .collect();
+            Ok(format!(
+                "{} bags can contain {}: {}",
+                ancestors.len(),
+                bags.name(id),
+                names.join(", ")
+            ))
+        }
+        "contains" => {
+            let id = lookup(bags, rest)?;
+            Ok(format!(
+                "{} bags contain {} other bags",
+                bags.name(id),
+                bags.total_contained(bags.name(id))?
+            ))
+        }
+        "path" => {
+            let (from, to) = rest
+                .split_once("->")
+                .ok_or_else(|| "Usage: path <colour> -> <colour>".to_string())?;
+            let (from, to) = (lookup(bags, from)?, lookup(bags, to)?);
+            match bags.shortest_chain(from, to) {
+                Some(chain) => {
+                    let names: Vec<&str> = chain.into_iter().map(|id| bags.name(id)).collect();
+                    Ok(names.join(" -> "))
                 }
+                None => Ok(format!(
+                    "{} bags cannot contain {} bags",
+                    bags.name(from),
+                    bags.name(to)
+                )),
+            }
+        }
+        "paths" => {
+            let (from, to) = rest
+                .split_once("->")
+                .ok_or_else(|| "Usage: paths <colour> -> <colour> [limit]".to_string())?;
+            // a trailing number limits how many simple paths are listed
+            let (to, limit) = match to.trim().rsplit_once(' ') {
+                Some((colour, limit)) if limit.parse::<usize>().is_ok() => {
+                    (colour, limit.parse().unwrap())
+                }
+                _ => (to, DEFAULT_PATH_LIMIT),
             };
+            let (from, to) = (lookup(bags, from)?, lookup(bags, to)?);
+            Ok(describe_paths(bags, from, to, limit))
         }
+        "depth" => {
+            let id = lookup(bags, rest)?;
+            Ok(format!(
+                "{} bags nest {} deep",
+                bags.name(id),
+                levels(bags.nesting_depth(id))
+            ))
+        }
+        "stats" => Ok(summary(bags)),
+        "help" => Ok(REPL_HELP.to_string()),
+        _ => Err(format!("Unknown command {:?}, try help", command)),
     }
+}
 
-    fn insert_bag(&mut self, bag: &mut Bag) {
-        self.update_parents(bag);
+// The numbers worth checking first when a rules file changes.
+fn summary(bags: &BagIndex) -> String {
+    format!(
+        "{} colours, {} rules, {} containment edges\n\
+         {} roots, {} leaves, nesting at most {} deep",
+        bags.len(),
+        bags.rule_count(),
+        bags.total_edges(),
+        bags.roots().len(),
+        bags.leaves().len(),
+        levels(bags.max_nesting_depth().unwrap())
+    )
+}
 
-        match self.idx.get(&bag.name) {
-            Some(existing_bag) => {
-                bag.parents = existing_bag.parents.union(&bag.parents).cloned().collect();
-                bag.children = existing_bag.children.union(&bag.children).cloned().collect();
-            }
-            None => {}
-        };
-        self.idx.insert(bag.name.clone(), bag.clone());
+// Start of the word being completed and the candidates for it: command names
+// for the first word, colours after that (and after the `->` of `path`).
+fn completions(line: &str, colours: &[String]) -> (usize, Vec<String>) {
+    let (start, candidates): (usize, Vec<&str>) = match line.find(' ') {
+        None => (0, REPL_COMMANDS.to_vec()),
+        Some(space) => {
+            let start = match line.rfind("->") {
+                Some(arrow) => arrow + 2,
+                None => space,
+            };
+            let start = start + line[start..].len() - line[start..].trim_start().len();
+            (start, colours.iter().map(String::as_str).collect())
+        }
+    };
+
+    let prefix = &line[start..];
+    let mut matches: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(prefix))
+        .map(str::to_string)
+        .collect();
+    matches.sort();
+    (start, matches)
+}
+
+struct ReplHelper {
+    colours: Vec<String>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(completions(&line[..pos], &self.colours))
     }
 }
 
-fn collect_parents<'a>(bag: &'a Bag, bags: &'a BagIndex) -> HashSet<&'a Bag>{
-    let mut count = HashSet::new();
-    let parents = &bag.parents;
-    if parents.len() == 0 {
-        return count;
-    } 
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
 
-    count.insert(bag);
+fn repl(bags: &BagIndex) -> rustyline::Result<()> {
+    let mut editor: Editor<ReplHelper, _> = Editor::new()?;
+    editor.set_helper(Some(ReplHelper {
+        colours: bags.names().to_vec(),
+    }));
+
+    loop {
+        let line = match editor.readline("bags> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err),
+        };
+
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => break,
+            _ => {}
+        }
 
-    for parent_name in parents {
-        let parent = bags.idx.get(parent_name).unwrap();
-        count.insert(&parent);
-        count.extend(&collect_parents(parent, bags));
+        editor.add_history_entry(line.as_str())?;
+        match run_command(bags, &line) {
+            Ok(output) => println!("{}", output),
+            Err(err) => println!("{}", err),
+        }
     }
 
-    count
+    Ok(())
 }
 
-fn count_parents(bag: &Bag, bags: &BagIndex) -> usize {
-    collect_parents(bag, bags).len() - 1 // also counts itself...
+struct Config {
+    filename: String,
+    colour: String,
+    max_depth: Option<usize>,
+    dot: Option<String>,
+    json: Option<String>,
+    subgraph: Subgraph,
+    repl: bool,
+    to: Option<String>,
+    path_limit: usize,
+    show_order: bool,
+}
+
+// day7 <file> [colour] [--depth N] [--dot <out.dot>] [--json <out.json>]
+//      [--subgraph all|ancestors|descendants] [--repl]
+//      [--to <colour> [--paths N]] [--order]
+// with the colour defaulting to "shiny gold"
+fn parse_args(args: Vec<String>) -> Config {
+    let mut args = args.into_iter();
+    let mut positional: Vec<String> = vec![];
+    let mut max_depth = None;
+    let mut dot = None;
+    let mut json = None;
+    let mut subgraph = Subgraph::All;
+    let mut repl = false;
+    let mut to = None;
+    let mut path_limit = DEFAULT_PATH_LIMIT;
+    let mut show_order = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--depth" => max_depth = args.next().map(|d| d.parse().unwrap()),
+            "--dot" => dot = args.next(),
+            "--json" => json = args.next(),
+            "--subgraph" => subgraph = args.next().unwrap().parse().unwrap(),
+            "--repl" => repl = true,
+            "--to" => to = args.next(),
+            "--paths" => path_limit = args.next().unwrap().parse().unwrap(),
+            "--order" => show_order = true,
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut colour = "shiny gold".to_string();
+    if positional.len() > 1 {
+        colour = positional[1..].join(" ");
+    }
+
+    Config {
+        filename: positional[0].clone(),
+        colour,
+        max_depth,
+        dot,
+        json,
+        subgraph,
+        repl,
+        to,
+        path_limit,
+        show_order,
+    }
 }
 
 fn main() -> std::io::Result<()> {
-    let filename = aoc_2020::parse_simple_args();
-    let mut bags = BagIndex::new();
+    let args = aoc_2020::report::run_if_json::<Day7>();
+    let Config {
+        filename,
+        colour,
+        max_depth,
+        dot,
+        json,
+        subgraph,
+        repl: start_repl,
+        to,
+        path_limit,
+        show_order,
+    } = parse_args(args);
+    let bags = match Day7::parse(&fs::read_to_string(filename)?) {
+        Ok(bags) => bags,
+        Err(errors) => {
+            eprintln!("{}", errors);
+            process::exit(1);
+        }
+    };
+    for warning in bags.undefined() {
+        eprintln!("warning: {}, treating them as empty", warning);
+    }
+
+    if start_repl {
+        repl(&bags).map_err(std::io::Error::other)?;
+        return Ok(());
+    }
+
+    println!("{}", summary(&bags));
+    if show_order {
+        let depths = bags.depths_from_roots().unwrap();
+        for id in bags.topological_order().unwrap() {
+            println!("{:>4} {}", depths[id], bags.name(id));
+        }
+    }
+
+    let id = match bags.get(&colour) {
+        Some(id) => id,
+        None => {
+            println!("No rule mentions a {} bag", colour);
+            return Ok(());
+        }
+    };
 
-    if let Ok(lines) = aoc_2020::read_lines(filename) {
-        for line in lines {
-            let mut bag: Bag = line.unwrap().parse().unwrap();
-            bags.insert_bag(&mut bag);
+    let children: Vec<(u32, &str)> = bags
+        .children(id)
+        .iter()
+        .map(|&(count, child)| (count, bags.name(child)))
+        .collect();
+    println!("{} contains: {:?}", colour, children);
+    match max_depth {
+        Some(depth) => {
+            let ancestors = bags.ancestors(id, max_depth);
+            let ancestor_names: Vec<&str> = ancestors
+                .order()
+                .into_iter()
+                .map(|id| bags.name(id))
+                .collect();
+            println!(
+                "Count parents within {} levels: {} {:?}",
+                depth,
+                ancestors.len(),
+                ancestor_names
+            );
+            println!(
+                "Count bag colours within {} levels: {}",
+                depth,
+                bags.descendants(id, max_depth).len()
+            );
         }
+        None => println!(
+            "Count all parents: {}",
+            bags.count_parents(&colour).unwrap()
+        ),
+    }
+    match bags.total_contained(&colour) {
+        Ok(total) => println!("Count all bags inside: {}", total),
+        Err(err) => println!("{}", err),
     }
 
-    let shiny_gold = bags.idx.get("shiny gold").unwrap();
+    if let Some(to) = to {
+        match bags.get(&to) {
+            Some(to) => println!("{}", describe_paths(&bags, id, to, path_limit)),
+            None => println!("No rule mentions a {} bag", to),
+        }
+    }
 
-    println!("Shiny Gold: {:?}", shiny_gold);
-    println!("Count all parents: {}", count_parents(shiny_gold, &bags));
+    let include = bags.subgraph(id, subgraph);
+    if let Some(path) = dot {
+        fs::write(&path, bags.to_dot(Some(id), &include))?;
+        println!("Graph written to {}", path);
+    }
+    if let Some(path) = json {
+        fs::write(&path, bags.to_json(&include))?;
+        println!("Adjacency lists written to {}", path);
+    }
 
     Ok(())
 }
@@ -130,72 +424,144 @@ fn main() -> std::io::Result<()> {
 mod test {
     use super::*;
 
+    const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
     #[test]
-    fn test_from_str() {
-        let test_str = "shiny tan bags contain 1 shiny gold bag.";
-        let expected_bag = Bag {
-            name: "shiny tan".to_string(),
-            children: ["shiny gold".to_string()].iter().cloned().collect(),
-            ..Default::default()
-        };
+    fn test_run_command() {
+        let bags = Day7::parse(EXAMPLE).unwrap();
 
-        let bag: Bag = test_str.parse().unwrap();
-        assert_eq!(expected_bag, bag);
+        assert_eq!(
+            run_command(&bags, "parents shiny gold"),
+            Ok(
+                "4 bags can contain shiny gold: bright white, muted yellow, light red, dark orange"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            run_command(&bags, "contains shiny gold"),
+            Ok("shiny gold bags contain 32 other bags".to_string())
+        );
+        assert_eq!(
+            run_command(&bags, "path light red -> shiny gold"),
+            Ok("light red -> bright white -> shiny gold".to_string())
+        );
+        assert_eq!(
+            run_command(&bags, "path shiny gold -> light red"),
+            Ok("shiny gold bags cannot contain light red bags".to_string())
+        );
+        assert_eq!(
+            run_command(&bags, "depth dark olive"),
+            Ok("dark olive bags nest 1 level deep".to_string())
+        );
+        assert_eq!(
+            run_command(&bags, "depth light red"),
+            Ok("light red bags nest 4 levels deep".to_string())
+        );
+        assert_eq!(
+            run_command(&bags, "stats"),
+            Ok("9 colours, 9 rules, 13 containment edges\n\
+                2 roots, 2 leaves, nesting at most 4 levels deep"
+                .to_string())
+        );
+        assert!(run_command(&bags, "contains plaid").is_err());
+        assert!(run_command(&bags, "path light red").is_err());
+        assert!(run_command(&bags, "explode").is_err());
     }
 
     #[test]
-    fn test_insert_bag() {
-        let inputs = vec![
-            Bag{
-                name: "1".to_string(),
-                children: ["2".to_string()].iter().cloned().collect(),
-                ..Default::default()
-            },
-            Bag{
-                name: "2".to_string(),
-                children: ["4".to_string(), "3".to_string()].iter().cloned().collect(),
-                ..Default::default()
-            },
-            Bag{
-                name: "3".to_string(),
-                children: ["4".to_string()].iter().cloned().collect(),
-                ..Default::default()
-            },
-        ];
-
-        let mut bags = BagIndex::new();
-
-        for mut bag in inputs {
-            bags.insert_bag(&mut bag);
-        }
+    fn test_completions() {
+        let colours: Vec<String> = ["shiny gold", "shiny tan", "light red"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
 
-        for name in 1..4 {
-            let expected = match name {
-                1 => Bag{
-                    name: "1".to_string(),
-                    children: ["2".to_string()].iter().cloned().collect(),
-                    ..Default::default()
-                },
-                2 => Bag{
-                    name: "2".to_string(),
-                    parents: ["1".to_string()].iter().cloned().collect(),
-                    children: ["3".to_string(), "4".to_string()].iter().cloned().collect(),
-                },
-                3 => Bag{
-                    name: "3".to_string(),
-                    parents: ["2".to_string()].iter().cloned().collect(),
-                    children: ["4".to_string()].iter().cloned().collect(),
-                },
-                4 => Bag{
-                    name: "4".to_string(),
-                    parents: ["3".to_string(), "4".to_string()].iter().cloned().collect(),
-                    ..Default::default()
-                },
-                _ =>Bag{..Default::default()}
-            };
-            assert_eq!(Some(&expected), bags.idx.get(&expected.name));
-        }
+        assert_eq!(
+            completions("pa", &colours),
+            (
+                0,
+                vec![
+                    "parents".to_string(),
+                    "path".to_string(),
+                    "paths".to_string()
+                ]
+            )
+        );
+        assert_eq!(
+            completions("parents shi", &colours),
+            (8, vec!["shiny gold".to_string(), "shiny tan".to_string()])
+        );
+        assert_eq!(
+            completions("path light red -> shiny g", &colours),
+            (18, vec!["shiny gold".to_string()])
+        );
+        assert_eq!(
+            completions("depth  li", &colours),
+            (7, vec!["light red".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_paths_between_bags() {
+        let bags = Day7::parse(EXAMPLE).unwrap();
+        let light_red = bags.get("light red").unwrap();
+        let shiny_gold = bags.get("shiny gold").unwrap();
+        let faded_blue = bags.get("faded blue").unwrap();
+
+        assert!(bags.can_contain(light_red, shiny_gold));
+        assert!(!bags.can_contain(shiny_gold, light_red));
+        assert_eq!(bags.heaviest_chain(shiny_gold, light_red), None);
 
-        assert_eq!(count_parents(bags.idx.get("4").unwrap(), &bags), 3)
+        // light red -(1)-> bright white -(1)-> shiny gold holds 1 + 1 bags,
+        // light red -(2)-> muted yellow -(2)-> shiny gold holds 2 + 4
+        let (chain, total) = bags.heaviest_chain(light_red, shiny_gold).unwrap();
+        assert_eq!(
+            format_chain(&bags, &chain),
+            "light red -(2)-> muted yellow -(2)-> shiny gold"
+        );
+        assert_eq!(total, 6);
+
+        let paths: Vec<String> = bags
+            .simple_paths(light_red, faded_blue, 10)
+            .iter()
+            .map(|path| format_chain(&bags, path))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "light red -(1)-> bright white -(1)-> shiny gold -(1)-> dark olive -(3)-> faded blue",
+                "light red -(1)-> bright white -(1)-> shiny gold -(2)-> vibrant plum -(5)-> faded blue",
+                "light red -(2)-> muted yellow -(2)-> shiny gold -(1)-> dark olive -(3)-> faded blue",
+                "light red -(2)-> muted yellow -(2)-> shiny gold -(2)-> vibrant plum -(5)-> faded blue",
+                "light red -(2)-> muted yellow -(9)-> faded blue",
+            ]
+        );
+        assert_eq!(bags.simple_paths(light_red, faded_blue, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_describe_paths() {
+        let bags = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            run_command(&bags, "paths dark orange -> shiny gold 1"),
+            Ok("dark orange bags can contain shiny gold bags
+Shortest chain: dark orange -(3)-> bright white -(1)-> shiny gold
+Heaviest chain (12 bags): dark orange -(4)-> muted yellow -(2)-> shiny gold
+Simple paths (1, at most 1):
+  dark orange -(3)-> bright white -(1)-> shiny gold"
+                .to_string())
+        );
+        assert_eq!(
+            run_command(&bags, "paths faded blue -> shiny gold"),
+            Ok("faded blue bags cannot contain shiny gold bags".to_string())
+        );
     }
 }
@@ -0,0 +1,145 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::toml::{parse_value, strip_comment};
+
+pub const DEFAULT_CONFIG: &str = "aoc.toml";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+// Settings for talking to the puzzle site, read from `aoc.toml`:
+//
+//     session = "53616c74..."        # the `session` cookie from the browser
+//     base_url = "https://adventofcode.com"
+//     min_interval_ms = 5000         # minimum gap between requests
+//     state_dir = ".aoc"             # where request times are recorded
+//
+// Every key is optional, and `AOC_SESSION` / `AOC_BASE_URL` take precedence
+// over the file so the token never has to be written down.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub min_interval: Duration,
+    pub state_dir: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            min_interval: Duration::from_secs(5),
+            state_dir: PathBuf::from(".aoc"),
+        }
+    }
+}
+
+impl Config {
+    // Reads `path` if it exists, then applies the environment.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let config = match fs::read_to_string(path) {
+            Ok(contents) => contents
+                .parse()
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(format!("Could not read {}: {}", path.display(), e)),
+        };
+        Ok(config.with_env(|var| std::env::var(var).ok()))
+    }
+
+    pub fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> Self {
+        if let Some(session) = var(SESSION_VAR).filter(|s| !s.trim().is_empty()) {
+            self.session = Some(session.trim().to_string());
+        }
+        if let Some(base_url) = var(BASE_URL_VAR).filter(|s| !s.is_empty()) {
+            self.base_url = base_url;
+        }
+        self
+    }
+
+    pub fn session(&self) -> Result<&str, String> {
+        self.session.as_deref().ok_or_else(|| {
+            format!(
+                "No session token, set {} or `session` in {}",
+                SESSION_VAR, DEFAULT_CONFIG
+            )
+        })
+    }
+}
+
+impl FromStr for Config {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = Config::default();
+
+        for (idx, line) in s.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `key = value`", line_no))?;
+            let value = parse_value(value.trim(), line_no)?;
+            match key.trim() {
+                "session" => config.session = Some(value),
+                "base_url" => config.base_url = value,
+                "min_interval_ms" => {
+                    let ms = value
+                        .parse()
+                        .map_err(|_| format!("line {}: invalid interval {:?}", line_no, value))?;
+                    config.min_interval = Duration::from_millis(ms);
+                }
+                "state_dir" => config.state_dir = PathBuf::from(value),
+                key => return Err(format!("line {}: unknown key {:?}", line_no, key)),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let config: Config = "session = \"abc123\"  # from the browser
+min_interval_ms = 250
+"
+        .parse()
+        .unwrap();
+        assert_eq!(config.session(), Ok("abc123"));
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+        assert_eq!(config.min_interval, Duration::from_millis(250));
+
+        assert!("sesion = \"abc\"".parse::<Config>().is_err());
+        assert!("min_interval_ms = \"soon\"".parse::<Config>().is_err());
+        assert!(Config::default().session().is_err());
+    }
+
+    #[test]
+    fn test_with_env() {
+        let config: Config = "session = \"from-file\"".parse().unwrap();
+        let env = |var: &str| match var {
+            SESSION_VAR => Some("from-env\n".to_string()),
+            BASE_URL_VAR => Some("http://127.0.0.1:8080".to_string()),
+            _ => None,
+        };
+
+        let config = config.with_env(env);
+        assert_eq!(config.session(), Ok("from-env"));
+        assert_eq!(config.base_url, "http://127.0.0.1:8080");
+        assert_eq!(config.clone().with_env(|_| None), config);
+    }
+}
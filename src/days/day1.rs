@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use crate::Solver;
+
+pub fn sum_of_two(nums: &[u32]) -> Option<u32> {
+    let mut val_map: HashMap<u32, u32> = HashMap::new();
+
+    for &num in nums {
+        if val_map.contains_key(&num) {
+            return Some(val_map.get(&num).unwrap() * num);
+        }
+        // an entry above 2020 can't be part of a pair
+        if let Some(complement) = 2020u32.checked_sub(num) {
+            val_map.insert(complement, num);
+        }
+    }
+    None
+}
+
+pub fn sum_of_three(nums: &[u32]) -> Option<u32> {
+    let mut val_map: HashMap<u32, u32> = HashMap::new();
+
+    for i in nums {
+        // an entry above 2020 can't be part of a triple
+        let num = match 2020u32.checked_sub(*i) {
+            Some(num) => num,
+            None => continue,
+        };
+        for j in nums {
+            // first check if the number is in our HashMap of possible valid answers
+            // then look in our HashMap
+            if val_map.contains_key(&num) {
+                // return the product of the values
+                return Some(val_map.get(&num).unwrap() * i);
+            } else {
+                // Save the partial sum as a candidate pair
+                // we only care, of course if the sum is smalller than 2020
+                // otherwise the solution is eliminated
+                match i.checked_add(*j) {
+                    Some(partial_sum) if 2020 > partial_sum => {
+                        val_map.insert(partial_sum, i * j);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    None
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.parse().map_err(|e| format!("{:?}: {}", line, e)))
+            .collect()
+    }
+
+    fn part1(nums: &Self::Input) -> Result<String, String> {
+        sum_of_two(nums)
+            .map(|answer| answer.to_string())
+            .ok_or_else(|| "No two entries sum to 2020".to_string())
+    }
+
+    fn part2(nums: &Self::Input) -> Result<String, String> {
+        sum_of_three(nums)
+            .map(|answer| answer.to_string())
+            .ok_or_else(|| "No three entries sum to 2020".to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    crate::example_tests!(Day1 { part1, part2 });
+
+    const EXAMPLE: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+    #[test]
+    fn test_example() {
+        let nums = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part1(&nums), Ok("514579".to_string()));
+        assert_eq!(Day1::part2(&nums), Ok("241861950".to_string()));
+        assert_eq!(Day1::solve(EXAMPLE, 2), Ok("241861950".to_string()));
+        assert!(Day1::solve(EXAMPLE, 3).is_err());
+        assert!(Day1::parse("1721\nabc\n").is_err());
+    }
+
+    #[test]
+    fn test_entries_above_2020() {
+        let nums = Day1::parse("3000\n1721\n4294967295\n299\n").unwrap();
+        assert_eq!(Day1::part1(&nums), Ok("514579".to_string()));
+        assert_eq!(
+            Day1::part2(&nums),
+            Err("No three entries sum to 2020".to_string())
+        );
+
+        let nums = Day1::parse("3000\n979\n366\n675\n").unwrap();
+        assert_eq!(Day1::part2(&nums), Ok("241861950".to_string()));
+        assert_eq!(
+            Day1::part1(&nums),
+            Err("No two entries sum to 2020".to_string())
+        );
+    }
+}
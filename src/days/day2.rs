@@ -0,0 +1,217 @@
+use std::str::Split;
+
+use crate::Solver;
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct ValidPassword {
+    pub min: u8,
+    pub max: u8,
+    pub c: char,
+    pub password: String,
+}
+
+impl ValidPassword {
+    pub fn is_valid(&self) -> bool {
+        let count = self.password.matches(self.c).count() as u8;
+        self.min <= count && count <= self.max
+    }
+
+    // Positions are 1-based, there is no position 0
+    fn get_char_at_idx(&self, idx: u8) -> Option<char> {
+        self.password.chars().nth((idx as usize).checked_sub(1)?)
+    }
+
+    pub fn is_valid_pt2(&self) -> bool {
+        let a = self.get_char_at_idx(self.min);
+        let b = self.get_char_at_idx(self.max);
+
+        (a == Some(self.c) || b == Some(self.c)) && a != b
+    }
+}
+
+fn next_field<'a>(split: &mut Split<'a, char>, what: &str) -> Result<&'a str, String> {
+    split
+        .next()
+        .filter(|field| !field.is_empty())
+        .ok_or_else(|| format!("Missing {}", what))
+}
+
+fn parse_first_char(split: &mut Split<char>) -> Result<char, String> {
+    let field = next_field(split, "policy letter")?;
+    match field
+        .strip_suffix(':')
+        .unwrap_or(field)
+        .chars()
+        .collect::<Vec<char>>()[..]
+    {
+        [c] => Ok(c),
+        _ => Err(format!("Invalid policy letter {:?}", field)),
+    }
+}
+
+fn parse_u8(split: &mut Split<char>, what: &str) -> Result<u8, String> {
+    let field = next_field(split, what)?;
+    field
+        .parse()
+        .map_err(|_| format!("Invalid {} {:?}", what, field))
+}
+
+fn parse_password(split: &mut Split<char>) -> Result<String, String> {
+    Ok(next_field(split, "password")?.to_string())
+}
+
+fn parse_min_max(split: &mut Split<char>) -> Result<(u8, u8), String> {
+    let mut minmax = next_field(split, "policy range")?.split('-');
+    let min: u8 = parse_u8(&mut minmax, "minimum")?;
+    let max: u8 = parse_u8(&mut minmax, "maximum")?;
+
+    Ok((min, max))
+}
+
+// A `<min>-<max> <letter>: <password>` line
+pub fn parse_line(i: String) -> Result<ValidPassword, String> {
+    let mut split = i.split(' ');
+
+    let (min, max) = parse_min_max(&mut split)?;
+    let c = parse_first_char(&mut split)?;
+    let password = parse_password(&mut split)?;
+    if split.next().is_some() {
+        return Err(format!("Unexpected text after the password in {:?}", i));
+    }
+
+    Ok(ValidPassword {
+        min,
+        max,
+        c,
+        password,
+    })
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<ValidPassword>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(idx, line)| {
+                parse_line(line.to_string()).map_err(|e| format!("line {}: {}", idx + 1, e))
+            })
+            .collect()
+    }
+
+    fn part1(passwords: &Self::Input) -> Result<String, String> {
+        let count = passwords.iter().filter(|p| p.is_valid()).count();
+        Ok(count.to_string())
+    }
+
+    fn part2(passwords: &Self::Input) -> Result<String, String> {
+        let count = passwords.iter().filter(|p| p.is_valid_pt2()).count();
+        Ok(count.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!(Day2 { part1, part2 });
+    /* test cases
+     *
+     * 2-4 c: cbccc
+     * 4-5 q: cwmhd
+     */
+
+    #[test]
+    fn test_valid_password() {
+        let pw = ValidPassword {
+            min: 2,
+            max: 4,
+            c: 'c',
+            password: "cbccc".to_string(),
+        };
+        assert!(pw.is_valid());
+    }
+
+    #[test]
+    fn test_invalid_password() {
+        let pw = ValidPassword {
+            min: 4,
+            max: 5,
+            c: 'q',
+            password: "cwmhd".to_string(),
+        };
+        assert!(!pw.is_valid());
+    }
+
+    #[test]
+    fn test_is_valid_pt2() {
+        let pw1 = ValidPassword {
+            min: 1,
+            max: 3,
+            c: 'a',
+            password: "abcde".to_string(),
+        };
+
+        let pw2 = ValidPassword {
+            min: 1,
+            max: 3,
+            c: 'b',
+            password: "cdefg".to_string(),
+        };
+
+        let pw3 = ValidPassword {
+            min: 2,
+            max: 9,
+            c: 'c',
+            password: "ccccccccc".to_string(),
+        };
+        assert!(pw1.is_valid_pt2());
+        assert!(!pw2.is_valid_pt2());
+        assert!(!pw3.is_valid_pt2());
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let input = "2-4 c: cbccc".to_string();
+        let pw = ValidPassword {
+            min: 2,
+            max: 4,
+            c: 'c',
+            password: "cbccc".to_string(),
+        };
+        assert_eq!(parse_line(input), Ok(pw));
+    }
+
+    #[test]
+    fn test_parse_line_malformed() {
+        for input in [
+            "garbage",
+            "",
+            "2-4",
+            "2-4 c:",
+            "2 c: cbccc",
+            "2-x c: cbccc",
+            "2-400 c: cbccc",
+            "2-4 cc: cbccc",
+            "2-4 c: cbccc extra",
+        ] {
+            assert!(parse_line(input.to_string()).is_err(), "{:?}", input);
+        }
+        assert_eq!(
+            Day2::parse("1-3 a: abcde\ngarbage\n"),
+            Err("line 2: Invalid minimum \"garbage\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_position_zero() {
+        let pw = parse_line("0-2 a: ba".to_string()).unwrap();
+        assert!(pw.is_valid());
+        assert!(pw.is_valid_pt2());
+    }
+}
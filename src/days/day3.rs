@@ -0,0 +1,79 @@
+use crate::Solver;
+
+// The slopes checked for part 2, as (right, down)
+pub const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+// Trees hit going `right` and `down` each step, the map repeats to the right.
+pub fn count_trees(lines: &[String], right: usize, down: usize) -> usize {
+    let mut idx = 0;
+    let mut count = 0;
+
+    for line in lines.iter().step_by(down) {
+        if line.as_bytes()[idx] == b'#' {
+            count += 1;
+        }
+
+        idx = (idx + right) % line.len();
+    }
+
+    count
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<String>;
+
+    // Every row of the map has to be the same width
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        let lines: Vec<String> = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+        if let Some(first) = lines.first() {
+            if let Some((idx, line)) = lines
+                .iter()
+                .enumerate()
+                .find(|(_, line)| line.len() != first.len())
+            {
+                return Err(format!(
+                    "Row {} is {} wide, expected {}",
+                    idx + 1,
+                    line.len(),
+                    first.len()
+                ));
+            }
+        }
+        Ok(lines)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<String, String> {
+        Ok(count_trees(lines, 3, 1).to_string())
+    }
+
+    fn part2(lines: &Self::Input) -> Result<String, String> {
+        let product: u64 = SLOPES
+            .iter()
+            .map(|&(right, down)| count_trees(lines, right, down) as u64)
+            .product();
+        Ok(product.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    crate::example_tests!(Day3 { part1, part2 });
+
+    #[test]
+    fn test_parse_ragged_rows() {
+        assert_eq!(
+            Day3::parse("..#\n#.\n"),
+            Err("Row 2 is 2 wide, expected 3".to_string())
+        );
+        assert_eq!(Day3::parse("..#\n#..\n").unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,266 @@
+use core::str::FromStr;
+use std::collections::HashSet;
+
+use lazy_static::lazy_static;
+
+use crate::Solver;
+
+lazy_static! {
+    static ref VALID_ECL: HashSet<&'static str> = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
+        .iter()
+        .cloned()
+        .collect();
+}
+
+// Every field but cid has to be present for part 1
+const REQUIRED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+#[derive(Debug, Default)]
+pub struct Passport {
+    fields: HashSet<String>,
+    pid: Option<String>,
+    cid: Option<String>,
+    byr: Option<u16>,
+    iyr: Option<u16>,
+    eyr: Option<u16>,
+    hgt: Option<String>,
+    hcl: Option<String>,
+    ecl: Option<String>,
+}
+
+impl Passport {
+    pub fn has_required_fields(&self) -> bool {
+        REQUIRED_FIELDS
+            .iter()
+            .all(|&field| self.fields.contains(field))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        !(self.pid.is_none()
+            || self.byr.is_none()
+            || self.iyr.is_none()
+            || self.eyr.is_none()
+            || self.hgt.is_none()
+            || self.hcl.is_none()
+            || self.ecl.is_none())
+    }
+
+    fn parse_pid(&mut self, value: &str) {
+        self.pid = match value.parse::<String>() {
+            Ok(v) if v.chars().all(char::is_numeric) && v.len() == 9 => Some(v),
+            _ => None,
+        }
+    }
+
+    fn parse_cid(&mut self, value: &str) {
+        self.cid = value.parse::<String>().ok()
+    }
+
+    fn parse_byr(&mut self, value: &str) {
+        self.byr = match value.parse() {
+            Ok(v) if (1920..=2002).contains(&v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn parse_iyr(&mut self, value: &str) {
+        self.iyr = match value.parse() {
+            Ok(v) if (2010..=2020).contains(&v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn parse_eyr(&mut self, value: &str) {
+        self.eyr = match value.parse() {
+            Ok(v) if (2020..=2030).contains(&v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn parse_hgt(&mut self, value: &str) {
+        let valid = match (value.strip_suffix("cm"), value.strip_suffix("in")) {
+            (Some(cm), _) => cm.parse().is_ok_and(|cm: u16| (150..=193).contains(&cm)),
+            (_, Some(inches)) => inches.parse().is_ok_and(|i: u16| (59..=76).contains(&i)),
+            _ => false,
+        };
+
+        if valid {
+            self.hgt = Some(value.to_string());
+        }
+    }
+
+    fn parse_hcl(&mut self, value: &str) {
+        self.hcl = match value.strip_prefix('#') {
+            Some(hex)
+                if hex.len() == 6 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')) =>
+            {
+                Some(value.to_string())
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_ecl(&mut self, value: &str) {
+        self.ecl = match value.parse::<String>() {
+            Ok(v) => {
+                if VALID_ECL.contains(v.as_str()) {
+                    Some(v)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Passport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut passport: Passport = Default::default();
+
+        let input = s.split(' ');
+        for kv in input {
+            let mut kv_parsed = kv.split(':');
+            let (key, value) = (kv_parsed.next(), kv_parsed.next());
+            if let (Some(key), Some(_)) = (key, value) {
+                passport.fields.insert(key.to_string());
+            }
+            match (key, value) {
+                (Some("pid"), Some(value)) => passport.parse_pid(value),
+                (Some("cid"), Some(value)) => passport.parse_cid(value),
+                (Some("byr"), Some(value)) => passport.parse_byr(value),
+                (Some("iyr"), Some(value)) => passport.parse_iyr(value),
+                (Some("eyr"), Some(value)) => passport.parse_eyr(value),
+                (Some("hgt"), Some(value)) => passport.parse_hgt(value),
+                (Some("hcl"), Some(value)) => passport.parse_hcl(value),
+                (Some("ecl"), Some(value)) => passport.parse_ecl(value),
+                _ => {}
+            }
+        }
+
+        Ok(passport)
+    }
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<Passport>;
+
+    // Passports are separated by blank lines and may span several lines.
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        input
+            .split("\n\n")
+            .map(|block| block.split_whitespace().collect::<Vec<&str>>().join(" "))
+            .filter(|block| !block.is_empty())
+            .map(|block| block.parse())
+            .collect()
+    }
+
+    fn part1(passports: &Self::Input) -> Result<String, String> {
+        Ok(passports
+            .iter()
+            .filter(|passport| passport.has_required_fields())
+            .count()
+            .to_string())
+    }
+
+    fn part2(passports: &Self::Input) -> Result<String, String> {
+        Ok(passports
+            .iter()
+            .filter(|passport| passport.is_valid())
+            .count()
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    crate::example_tests!(Day4 {
+        part1,
+        part2,
+        part2_invalid
+    });
+
+    #[test]
+    fn test_has_required_fields() {
+        let passport: Passport =
+            "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 hgt:183cm"
+                .parse()
+                .unwrap();
+        assert!(passport.has_required_fields());
+
+        // invalid values still count as present for part 1
+        let passport: Passport = "ecl:zzz pid:1 eyr:1 hcl:z byr:1 iyr:1 hgt:1"
+            .parse()
+            .unwrap();
+        assert!(passport.has_required_fields());
+        assert!(!passport.is_valid());
+
+        let passport: Passport =
+            "ecl:gry pid:860033327 eyr:2020 byr:1937 iyr:2017 cid:147 hgt:183cm"
+                .parse()
+                .unwrap();
+        assert!(!passport.has_required_fields());
+    }
+
+    #[test]
+    fn test_height_bounds() {
+        for (hgt, valid) in &[
+            ("149cm", false),
+            ("150cm", true),
+            ("193cm", true),
+            ("194cm", false),
+            ("58in", false),
+            ("59in", true),
+            ("76in", true),
+            ("77in", false),
+            ("190", false),
+            ("abccm", false),
+        ] {
+            let mut passport = Passport::default();
+            passport.parse_hgt(hgt);
+            assert_eq!(passport.hgt.is_some(), *valid, "{}", hgt);
+        }
+    }
+
+    // the valid and invalid examples from the puzzle text
+    #[test]
+    fn test_puzzle_examples() {
+        let mut passport = Passport::default();
+        passport.parse_byr("2002");
+        assert_eq!(passport.byr, Some(2002));
+        passport.parse_byr("2003");
+        assert_eq!(passport.byr, None);
+
+        passport.parse_pid("000000001");
+        assert!(passport.pid.is_some());
+        passport.parse_pid("0123456789");
+        assert!(passport.pid.is_none());
+
+        passport.parse_ecl("brn");
+        assert!(passport.ecl.is_some());
+        passport.parse_ecl("wat");
+        assert!(passport.ecl.is_none());
+    }
+
+    #[test]
+    fn test_hair_colour() {
+        for (hcl, valid) in &[
+            ("#123abc", true),
+            ("#123abz", false),
+            ("123abc", false),
+            ("#123ab", false),
+            ("", false),
+        ] {
+            let mut passport = Passport::default();
+            passport.parse_hcl(hcl);
+            assert_eq!(passport.hcl.is_some(), *valid, "{}", hcl);
+        }
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::Solver;
+
+// Describes how a boarding pass maps onto an aircraft: how many characters
+// encode the row and the seat, which letter picks the lower/upper half for
+// each, and the multiplier used for the seat id (`row * id_multiplier + seat`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeatLayout {
+    pub row_bits: u32,
+    pub seat_bits: u32,
+    pub row_chars: (char, char),
+    pub seat_chars: (char, char),
+    pub id_multiplier: u32,
+}
+
+impl Default for SeatLayout {
+    fn default() -> Self {
+        SeatLayout {
+            row_bits: 7,
+            seat_bits: 3,
+            row_chars: ('F', 'B'),
+            seat_chars: ('L', 'R'),
+            id_multiplier: 8,
+        }
+    }
+}
+
+impl SeatLayout {
+    // Layout with the default letters for a plane of `rows` x `seats`,
+    // both of which must be powers of two.
+    pub fn with_dimensions(rows: u32, seats: u32) -> Result<Self, String> {
+        if !rows.is_power_of_two() || !seats.is_power_of_two() {
+            return Err(format!(
+                "Plane dimensions must be powers of two, got {}x{}",
+                rows, seats
+            ));
+        }
+
+        Ok(SeatLayout {
+            row_bits: rows.trailing_zeros(),
+            seat_bits: seats.trailing_zeros(),
+            id_multiplier: seats,
+            ..Default::default()
+        })
+    }
+
+    pub fn rows(&self) -> u32 {
+        1 << self.row_bits
+    }
+
+    pub fn seats(&self) -> u32 {
+        1 << self.seat_bits
+    }
+
+    pub fn seat_id(&self, row: u32, seat: u32) -> u32 {
+        row * self.id_multiplier + seat
+    }
+
+    pub fn parse_ticket(&self, s: &str) -> Result<PlaneTicket, String> {
+        let expected_len = (self.row_bits + self.seat_bits) as usize;
+        if s.chars().count() != expected_len {
+            return Err(format!(
+                "Boarding pass {:?} should have {} characters",
+                s, expected_len
+            ));
+        }
+
+        // split on a char boundary, a letter may be more than one byte
+        let split = s
+            .char_indices()
+            .nth(self.row_bits as usize)
+            .map_or(s.len(), |(idx, _)| idx);
+        let (row_part, seat_part) = s.split_at(split);
+        let row = decode_half(row_part, self.row_chars)?;
+        let seat = decode_half(seat_part, self.seat_chars)?;
+
+        Ok(PlaneTicket {
+            id: self.seat_id(row, seat),
+            row,
+            seat,
+        })
+    }
+}
+
+// Reads a binary space partition where `chars.0` is a 0 and `chars.1` is a 1.
+fn decode_half(s: &str, chars: (char, char)) -> Result<u32, String> {
+    s.chars().try_fold(0u32, |acc, c| match c {
+        c if c == chars.0 => Ok(acc << 1),
+        c if c == chars.1 => Ok((acc << 1) | 1),
+        c => Err(format!(
+            "Unexpected character {:?}, expected {:?} or {:?}",
+            c, chars.0, chars.1
+        )),
+    })
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct PlaneTicket {
+    pub id: u32,
+    pub row: u32,
+    pub seat: u32,
+}
+
+impl FromStr for PlaneTicket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SeatLayout::default().parse_ticket(s)
+    }
+}
+
+// Number of boarding passes scanned for every seat on the plane, stored row by row.
+pub struct SeatMap {
+    layout: SeatLayout,
+    counts: Vec<u32>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SeatReport {
+    // empty seats with both neighbouring ids taken
+    pub my_seat: Vec<u32>,
+    // empty seats before the first and after the last scanned id
+    pub missing_front: Vec<u32>,
+    pub missing_back: Vec<u32>,
+    // any other empty seat between the first and last scanned id
+    pub missing_other: Vec<u32>,
+    // ids scanned more than once
+    pub duplicates: Vec<u32>,
+}
+
+impl SeatMap {
+    pub fn new(layout: SeatLayout) -> Self {
+        let counts = vec![0; (layout.rows() * layout.seats()) as usize];
+        SeatMap { layout, counts }
+    }
+
+    pub fn insert(&mut self, ticket: &PlaneTicket) {
+        self.counts[(ticket.row * self.layout.seats() + ticket.seat) as usize] += 1;
+    }
+
+    // (id, count) for every seat on the plane, ordered by id
+    pub fn seats_by_id(&self) -> Vec<(u32, u32)> {
+        let seats = self.layout.seats();
+        let mut by_id: Vec<(u32, u32)> = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(idx, &count)| {
+                let idx = idx as u32;
+                (self.layout.seat_id(idx / seats, idx % seats), count)
+            })
+            .collect();
+        by_id.sort_unstable();
+        by_id
+    }
+
+    pub fn report(&self) -> SeatReport {
+        let seats = self.seats_by_id();
+        let taken: HashSet<u32> = seats
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let first = taken.iter().min().copied().unwrap_or(u32::MAX);
+        let last = taken.iter().max().copied().unwrap_or(0);
+
+        let mut report = SeatReport::default();
+        for (id, count) in seats {
+            match count {
+                0 if id < first => report.missing_front.push(id),
+                0 if id > last => report.missing_back.push(id),
+                0 if taken.contains(&(id - 1)) && taken.contains(&(id + 1)) => {
+                    report.my_seat.push(id)
+                }
+                0 => report.missing_other.push(id),
+                1 => {}
+                _ => report.duplicates.push(id),
+            }
+        }
+
+        report
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SeatState {
+    Empty,
+    Taken,
+    Duplicate,
+    Mine,
+}
+
+impl SeatState {
+    fn symbol(self) -> char {
+        match self {
+            SeatState::Empty => '.',
+            SeatState::Taken => '#',
+            SeatState::Duplicate => 'D',
+            SeatState::Mine => '@',
+        }
+    }
+
+    fn colour(self) -> &'static str {
+        match self {
+            SeatState::Empty => "#eeeeee",
+            SeatState::Taken => "#4a90d9",
+            SeatState::Duplicate => "#d0021b",
+            SeatState::Mine => "#f5a623",
+        }
+    }
+}
+
+const SVG_CELL: u32 = 12;
+const SVG_LABEL_WIDTH: u32 = 36;
+
+impl SeatMap {
+    fn state(&self, row: u32, seat: u32, report: &SeatReport) -> SeatState {
+        let id = self.layout.seat_id(row, seat);
+        match self.counts[(row * self.layout.seats() + seat) as usize] {
+            0 if report.my_seat.contains(&id) => SeatState::Mine,
+            0 => SeatState::Empty,
+            1 => SeatState::Taken,
+            _ => SeatState::Duplicate,
+        }
+    }
+
+    // One line per row, prefixed with the row number and with an aisle
+    // down the middle of the plane.
+    pub fn render_text(&self, report: &SeatReport) -> String {
+        let aisle = self.layout.seats() / 2;
+        let mut out = String::new();
+
+        for row in 0..self.layout.rows() {
+            write!(out, "{:>4} ", row).unwrap();
+            for seat in 0..self.layout.seats() {
+                if seat == aisle && aisle > 0 {
+                    out.push(' ');
+                }
+                out.push(self.state(row, seat, report).symbol());
+            }
+            out.push('\n');
+        }
+
+        writeln!(
+            out,
+            "{} taken, {} empty, {} duplicate, {} my seat",
+            SeatState::Taken.symbol(),
+            SeatState::Empty.symbol(),
+            SeatState::Duplicate.symbol(),
+            SeatState::Mine.symbol()
+        )
+        .unwrap();
+        out
+    }
+
+    pub fn render_svg(&self, report: &SeatReport) -> String {
+        let aisle = self.layout.seats() / 2;
+        let width = SVG_LABEL_WIDTH + (self.layout.seats() + 1) * SVG_CELL;
+        let height = self.layout.rows() * SVG_CELL;
+        let mut out = String::new();
+
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="{}">"#,
+            width,
+            height,
+            SVG_CELL - 2
+        )
+        .unwrap();
+
+        for row in 0..self.layout.rows() {
+            let y = row * SVG_CELL;
+            writeln!(
+                out,
+                r#"  <text x="{}" y="{}" text-anchor="end">{}</text>"#,
+                SVG_LABEL_WIDTH - 4,
+                y + SVG_CELL - 2,
+                row
+            )
+            .unwrap();
+
+            for seat in 0..self.layout.seats() {
+                let gap = if seat >= aisle && aisle > 0 { 1 } else { 0 };
+                let state = self.state(row, seat, report);
+                writeln!(
+                    out,
+                    r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"><title>row {} seat {} id {}</title></rect>"#,
+                    SVG_LABEL_WIDTH + (seat + gap) * SVG_CELL,
+                    y,
+                    SVG_CELL - 1,
+                    SVG_CELL - 1,
+                    state.colour(),
+                    row,
+                    seat,
+                    self.layout.seat_id(row, seat)
+                )
+                .unwrap();
+            }
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+pub fn parse_tickets(input: &str, layout: &SeatLayout) -> Result<Vec<PlaneTicket>, String> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| layout.parse_ticket(line))
+        .collect()
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    const DAY: u8 = 5;
+    type Input = Vec<PlaneTicket>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        parse_tickets(input, &SeatLayout::default())
+    }
+
+    fn part1(tickets: &Self::Input) -> Result<String, String> {
+        tickets
+            .iter()
+            .map(|ticket| ticket.id)
+            .max()
+            .map(|id| id.to_string())
+            .ok_or_else(|| "No boarding passes".to_string())
+    }
+
+    fn part2(tickets: &Self::Input) -> Result<String, String> {
+        let mut seat_map = SeatMap::new(SeatLayout::default());
+        for ticket in tickets {
+            seat_map.insert(ticket);
+        }
+
+        match seat_map.report().my_seat.as_slice() {
+            [id] => Ok(id.to_string()),
+            seats => Err(format!("Expected one free seat, found {:?}", seats)),
+        }
+    }
+}
+
+// Collapses sorted ids into "a-b" ranges so long runs stay readable.
+pub fn format_ranges(ids: &[u32]) -> String {
+    let mut ranges: Vec<(u32, u32)> = vec![];
+    for &id in ids {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == id => *end = id,
+            _ => ranges.push((id, id)),
+        }
+    }
+
+    let ranges: Vec<String> = ranges
+        .iter()
+        .map(|&(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect();
+
+    match ranges.is_empty() {
+        true => "none".to_string(),
+        false => ranges.join(", "),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    crate::example_tests!(Day5 { part1 });
+
+    #[test]
+    fn test_from_str() {
+        let test_inputs = ["BFFFBBFRRR", "FFFBBBFRRR", "BBFFBBFRLL"];
+        let expected = vec![
+            PlaneTicket {
+                id: 567,
+                row: 70,
+                seat: 7,
+            },
+            PlaneTicket {
+                id: 119,
+                row: 14,
+                seat: 7,
+            },
+            PlaneTicket {
+                id: 820,
+                row: 102,
+                seat: 4,
+            },
+        ];
+        for (input, res) in test_inputs.iter().zip(expected) {
+            assert_eq!(input.parse::<PlaneTicket>().unwrap(), res);
+        }
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let layout = SeatLayout::default();
+        assert_eq!(
+            layout.parse_ticket("BFFFBBFRRÉ"),
+            Err("Unexpected character 'É', expected 'L' or 'R'".to_string())
+        );
+        assert_eq!(
+            layout.parse_ticket("BFFFBBÉRRR"),
+            Err("Unexpected character 'É', expected 'F' or 'B'".to_string())
+        );
+        assert!(layout.parse_ticket("ÉÉÉÉÉ").is_err());
+    }
+
+    #[test]
+    fn test_larger_layout() {
+        let layout = SeatLayout::with_dimensions(256, 16).unwrap();
+        assert_eq!(layout.rows(), 256);
+        assert_eq!(layout.seats(), 16);
+
+        let ticket = layout.parse_ticket("BBBBBBBBRRRR").unwrap();
+        assert_eq!(
+            ticket,
+            PlaneTicket {
+                id: 255 * 16 + 15,
+                row: 255,
+                seat: 15,
+            }
+        );
+
+        let ticket = layout.parse_ticket("FFFFFFFBLLLR").unwrap();
+        assert_eq!(
+            ticket,
+            PlaneTicket {
+                id: 16 + 1,
+                row: 1,
+                seat: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_custom_letters() {
+        let layout = SeatLayout {
+            row_chars: ('0', '1'),
+            seat_chars: ('0', '1'),
+            ..Default::default()
+        };
+        assert_eq!(
+            layout.parse_ticket("1000110111").unwrap(),
+            "BFFFBBFRRR".parse::<PlaneTicket>().unwrap()
+        );
+    }
+
+    fn seat_map_from_ids(layout: SeatLayout, ids: &[u32]) -> SeatMap {
+        let mut seat_map = SeatMap::new(layout.clone());
+        for &id in ids {
+            seat_map.insert(&PlaneTicket {
+                id,
+                row: id / layout.seats(),
+                seat: id % layout.seats(),
+            });
+        }
+        seat_map
+    }
+
+    #[test]
+    fn test_seat_report() {
+        // 4 rows x 2 seats: ids 0..8, with 0 and 7 empty at the ends,
+        // 3 empty between two taken seats and 5 scanned twice
+        let layout = SeatLayout::with_dimensions(4, 2).unwrap();
+        let seat_map = seat_map_from_ids(layout, &[1, 2, 4, 5, 5, 6]);
+
+        assert_eq!(
+            seat_map.report(),
+            SeatReport {
+                my_seat: vec![3],
+                missing_front: vec![0],
+                missing_back: vec![7],
+                missing_other: vec![],
+                duplicates: vec![5],
+            }
+        );
+    }
+
+    #[test]
+    fn test_seat_report_gaps() {
+        // 2 and 3 are both missing, so neither has two taken neighbours
+        let layout = SeatLayout::with_dimensions(4, 2).unwrap();
+        let seat_map = seat_map_from_ids(layout, &[1, 4, 6]);
+        let report = seat_map.report();
+
+        assert_eq!(report.my_seat, vec![5]);
+        assert_eq!(report.missing_front, vec![0]);
+        assert_eq!(report.missing_back, vec![7]);
+        assert_eq!(report.missing_other, vec![2, 3]);
+        assert!(report.duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_render_text() {
+        let layout = SeatLayout::with_dimensions(4, 2).unwrap();
+        let seat_map = seat_map_from_ids(layout, &[1, 2, 4, 5, 5, 6]);
+        let report = seat_map.report();
+
+        assert_eq!(
+            seat_map.render_text(&report),
+            "   0 . #\n   1 # @\n   2 # D\n   3 # .\n\
+             # taken, . empty, D duplicate, @ my seat\n"
+        );
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let layout = SeatLayout::with_dimensions(4, 2).unwrap();
+        let seat_map = seat_map_from_ids(layout, &[1, 2, 4, 5, 5, 6]);
+        let svg = seat_map.render_svg(&seat_map.report());
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect ").count(), 8);
+        assert_eq!(svg.matches(SeatState::Mine.colour()).count(), 1);
+        assert_eq!(svg.matches(SeatState::Duplicate.colour()).count(), 1);
+    }
+
+    #[test]
+    fn test_format_ranges() {
+        assert_eq!(format_ranges(&[]), "none");
+        assert_eq!(format_ranges(&[4]), "4");
+        assert_eq!(format_ranges(&[0, 1, 2, 5, 7, 8]), "0-2, 5, 7-8");
+    }
+
+    #[test]
+    fn test_invalid_tickets() {
+        assert!("BFFFBBFRR".parse::<PlaneTicket>().is_err());
+        assert!("BFFFBBFRRX".parse::<PlaneTicket>().is_err());
+        assert!(SeatLayout::with_dimensions(100, 8).is_err());
+    }
+}
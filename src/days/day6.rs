@@ -0,0 +1,342 @@
+use std::io;
+use std::ops::{BitAnd, BitOr};
+use std::str::FromStr;
+
+use crate::Solver;
+
+// The questions a-z one or more people answered yes to, one bit per question.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AnswerSet(u32);
+
+impl AnswerSet {
+    pub const ALL: AnswerSet = AnswerSet((1 << 26) - 1);
+
+    fn bit(c: char) -> Result<u32, String> {
+        match c {
+            'a'..='z' => Ok(1 << (c as u32 - 'a' as u32)),
+            _ => Err(format!("Invalid question {:?}, expected a-z", c)),
+        }
+    }
+
+    pub fn insert(&mut self, c: char) -> Result<(), String> {
+        self.0 |= AnswerSet::bit(c)?;
+        Ok(())
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        AnswerSet::bit(c).is_ok_and(|bit| self.0 & bit != 0)
+    }
+
+    pub fn union(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & other.0)
+    }
+
+    pub fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn chars(self) -> impl Iterator<Item = char> {
+        ('a'..='z').filter(move |&c| self.contains(c))
+    }
+}
+
+impl BitOr for AnswerSet {
+    type Output = AnswerSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for AnswerSet {
+    type Output = AnswerSet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl FromStr for AnswerSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = AnswerSet::default();
+        for c in s.chars() {
+            answers.insert(c)?;
+        }
+        Ok(answers)
+    }
+}
+
+// How many people in a group must answer yes for a question to count.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Quorum {
+    AtLeast(usize),
+    Percent(u32),
+    Majority,
+    Everyone,
+}
+
+impl Quorum {
+    pub fn required(&self, group_size: usize) -> usize {
+        match *self {
+            Quorum::AtLeast(k) => k,
+            Quorum::Percent(p) => (group_size * p as usize).div_ceil(100),
+            Quorum::Majority => group_size / 2 + 1,
+            Quorum::Everyone => group_size,
+        }
+    }
+}
+
+impl FromStr for Quorum {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(Quorum::AtLeast(1)),
+            "all" => Ok(Quorum::Everyone),
+            "majority" => Ok(Quorum::Majority),
+            pct if pct.ends_with('%') => match pct.trim_end_matches('%').parse() {
+                Ok(p) if p <= 100 => Ok(Quorum::Percent(p)),
+                _ => Err(format!("Invalid percentage quorum: {}", s)),
+            },
+            k => match k.parse() {
+                Ok(k) => Ok(Quorum::AtLeast(k)),
+                _ => Err(format!("Invalid quorum: {}", s)),
+            },
+        }
+    }
+}
+
+// Per-question yes counts for a single group of people, along with the
+// questions anyone and everyone answered yes to.
+#[derive(Debug, PartialEq)]
+pub struct GroupStats {
+    pub size: usize,
+    pub counts: [usize; 26],
+    pub anyone: AnswerSet,
+    pub everyone: AnswerSet,
+}
+
+impl Default for GroupStats {
+    fn default() -> Self {
+        GroupStats {
+            size: 0,
+            counts: [0; 26],
+            anyone: AnswerSet::default(),
+            everyone: AnswerSet::ALL,
+        }
+    }
+}
+
+impl GroupStats {
+    pub fn add_person(&mut self, answers: AnswerSet) {
+        for c in answers.chars() {
+            self.counts[c as usize - 'a' as usize] += 1;
+        }
+        self.anyone = self.anyone | answers;
+        self.everyone = self.everyone & answers;
+        self.size += 1;
+    }
+
+    pub fn questions_meeting(&self, quorum: Quorum) -> AnswerSet {
+        let required = quorum.required(self.size).max(1);
+        ('a'..='z')
+            .zip(self.counts.iter())
+            .filter(|(_, &count)| count >= required)
+            .fold(AnswerSet::default(), |mut set, (c, _)| {
+                set.insert(c).unwrap();
+                set
+            })
+    }
+}
+
+// Groups are separated by blank lines, the last group is kept whether or
+// not the input ends with one.
+pub fn read_groups<I>(lines: I) -> Result<Vec<GroupStats>, String>
+where
+    I: Iterator<Item = io::Result<String>>,
+{
+    let mut groups = vec![];
+    let mut group = GroupStats::default();
+
+    for (idx, line) in lines.enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.is_empty() {
+            if group.size > 0 {
+                groups.push(group);
+            }
+            group = GroupStats::default();
+            continue;
+        }
+
+        let answers = line
+            .parse()
+            .map_err(|e| format!("line {}: {}", idx + 1, e))?;
+        group.add_person(answers);
+    }
+
+    if group.size > 0 {
+        groups.push(group);
+    }
+    Ok(groups)
+}
+
+pub fn count_quorum(groups: &[GroupStats], quorum: Quorum) -> u64 {
+    groups
+        .iter()
+        .map(|group| group.questions_meeting(quorum).len() as u64)
+        .sum()
+}
+
+pub fn check_anyone_yes(groups: &[GroupStats]) -> u64 {
+    groups.iter().map(|group| group.anyone.len() as u64).sum()
+}
+
+pub fn check_everyone_yes(groups: &[GroupStats]) -> u64 {
+    groups.iter().map(|group| group.everyone.len() as u64).sum()
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    const DAY: u8 = 6;
+    type Input = Vec<GroupStats>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        read_groups(input.lines().map(|line| Ok(line.to_string())))
+    }
+
+    fn part1(groups: &Self::Input) -> Result<String, String> {
+        Ok(check_anyone_yes(groups).to_string())
+    }
+
+    fn part2(groups: &Self::Input) -> Result<String, String> {
+        Ok(check_everyone_yes(groups).to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::BufRead;
+
+    crate::example_tests!(Day6 { part1, part2 });
+
+    const EXAMPLE: &str = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+
+    fn example_groups(input: &str) -> Vec<GroupStats> {
+        read_groups(input.as_bytes().lines()).unwrap()
+    }
+
+    #[test]
+    fn test_example_without_trailing_newline() {
+        let groups = example_groups(EXAMPLE);
+        assert_eq!(groups.len(), 5);
+        assert_eq!(check_anyone_yes(&groups), 11);
+        assert_eq!(check_everyone_yes(&groups), 6);
+    }
+
+    #[test]
+    fn test_example_with_trailing_newline() {
+        for ending in &["\n", "\n\n", "\n\n\n"] {
+            let groups = example_groups(&format!("{}{}", EXAMPLE, ending));
+            assert_eq!(groups.len(), 5);
+            assert_eq!(check_anyone_yes(&groups), 11);
+            assert_eq!(check_everyone_yes(&groups), 6);
+        }
+    }
+
+    #[test]
+    fn test_counts_do_not_overflow() {
+        // 3000 groups answering all 26 questions is well past u16::MAX
+        let input = vec!["abcdefghijklmnopqrstuvwxyz"; 3000].join("\n\n");
+        let groups = example_groups(&input);
+        assert_eq!(check_anyone_yes(&groups), 78_000);
+        assert_eq!(check_everyone_yes(&groups), 78_000);
+        assert_eq!(count_quorum(&groups, Quorum::Majority), 78_000);
+    }
+
+    fn group(people: &[&str]) -> GroupStats {
+        let mut group = GroupStats::default();
+        for person in people {
+            group.add_person(person.parse().unwrap());
+        }
+        group
+    }
+
+    fn answers(s: &str) -> AnswerSet {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_answer_set() {
+        let a = answers("abcx");
+        let b = answers("bxyz");
+        assert_eq!(a.len(), 4);
+        assert!(a.contains('x'));
+        assert!(!a.contains('y'));
+        assert_eq!(a | b, answers("abcxyz"));
+        assert_eq!(a & b, answers("bx"));
+        assert_eq!((a & b).chars().collect::<String>(), "bx");
+        assert_eq!(answers(""), AnswerSet::default());
+        assert_eq!(answers("aab"), answers("ba"));
+        assert_eq!(AnswerSet::ALL.len(), 26);
+    }
+
+    #[test]
+    fn test_answer_set_invalid() {
+        assert!("abC".parse::<AnswerSet>().is_err());
+        assert!("a b".parse::<AnswerSet>().is_err());
+        assert!(AnswerSet::default().insert('1').is_err());
+    }
+
+    #[test]
+    fn test_quorum_required() {
+        assert_eq!(Quorum::AtLeast(2).required(5), 2);
+        assert_eq!(Quorum::Percent(50).required(5), 3);
+        assert_eq!(Quorum::Percent(50).required(4), 2);
+        assert_eq!(Quorum::Majority.required(4), 3);
+        assert_eq!(Quorum::Majority.required(5), 3);
+        assert_eq!(Quorum::Everyone.required(5), 5);
+    }
+
+    #[test]
+    fn test_quorum_from_str() {
+        assert_eq!("any".parse(), Ok(Quorum::AtLeast(1)));
+        assert_eq!("all".parse(), Ok(Quorum::Everyone));
+        assert_eq!("majority".parse(), Ok(Quorum::Majority));
+        assert_eq!("3".parse(), Ok(Quorum::AtLeast(3)));
+        assert_eq!("60%".parse(), Ok(Quorum::Percent(60)));
+        assert!("120%".parse::<Quorum>().is_err());
+        assert!("most".parse::<Quorum>().is_err());
+    }
+
+    #[test]
+    fn test_questions_meeting() {
+        let group = group(&["abc", "ab", "a", "bd"]);
+        assert_eq!(group.size, 4);
+        assert_eq!(group.questions_meeting(Quorum::AtLeast(1)), group.anyone);
+        assert_eq!(group.questions_meeting(Quorum::AtLeast(1)), answers("abcd"));
+        assert_eq!(group.questions_meeting(Quorum::Majority), answers("ab"));
+        assert_eq!(group.questions_meeting(Quorum::Percent(50)), answers("ab"));
+        assert_eq!(group.questions_meeting(Quorum::Everyone), group.everyone);
+        assert_eq!(group.everyone, AnswerSet::default());
+    }
+
+    #[test]
+    fn test_repeated_answers_count_once() {
+        let group = group(&["aab", "a"]);
+        assert_eq!(group.counts[0], 2);
+        assert_eq!(group.questions_meeting(Quorum::Everyone), answers("a"));
+        assert_eq!(group.everyone, answers("a"));
+    }
+}
@@ -0,0 +1,1221 @@
+use core::str::FromStr;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fmt::Write;
+
+use crate::Solver;
+
+// A single parsed rule: a bag and how many of each other bag it must hold.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bag {
+    pub name: String,
+    pub children: Vec<(u32, String)>,
+}
+
+fn check_name(name: &str, rule: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_alphabetic() || c == ' ') {
+        return Err(format!("Invalid bag name {:?} in {:?}", name, rule));
+    }
+    Ok(())
+}
+
+// Parses a single `<count> <name> bag(s)` entry from the contents of a rule.
+fn parse_child(s: &str) -> Result<(u32, String), String> {
+    let (count, rest) = s
+        .split_once(' ')
+        .ok_or_else(|| format!("Expected \"<count> <name> bags\", got {:?}", s))?;
+    let count = count
+        .parse()
+        .map_err(|_| format!("Invalid bag count {:?} in {:?}", count, s))?;
+    let name = rest
+        .strip_suffix(" bags")
+        .or_else(|| rest.strip_suffix(" bag"))
+        .ok_or_else(|| format!("Expected {:?} to end in \"bag\" or \"bags\"", s))?;
+
+    check_name(name, s)?;
+    Ok((count, name.to_string()))
+}
+
+impl FromStr for Bag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rule = s
+            .trim()
+            .strip_suffix('.')
+            .ok_or_else(|| format!("Rule should end with a '.': {:?}", s))?;
+        let (name, contents) = rule
+            .split_once(" bags contain ")
+            .ok_or_else(|| format!("Rule is missing \"bags contain\": {:?}", s))?;
+
+        check_name(name, s)?;
+
+        let children = match contents {
+            "no other bags" => vec![],
+            contents => contents
+                .split(", ")
+                .map(parse_child)
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Bag {
+            name: name.to_string(),
+            children,
+        })
+    }
+}
+
+pub type BagId = usize;
+
+#[derive(Debug, PartialEq)]
+pub enum BagGraphError {
+    SelfLoop(String),
+    // the colours around the cycle, starting and ending with the same one
+    Cycle(Vec<String>),
+    Undefined {
+        name: String,
+        referenced_by: Vec<String>,
+    },
+}
+
+impl fmt::Display for BagGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagGraphError::SelfLoop(name) => write!(f, "{} bags contain themselves", name),
+            BagGraphError::Cycle(path) => write!(f, "Cycle in bag rules: {}", path.join(" -> ")),
+            BagGraphError::Undefined {
+                name,
+                referenced_by,
+            } => write!(
+                f,
+                "{} bags have no rule but are contained in: {}",
+                name,
+                referenced_by.join(", ")
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Parents,
+    Children,
+}
+
+// Bags reached from a starting bag (which is never included) in breadth
+// first order, along with how many edges away from the start each one is.
+#[derive(Debug, Default, PartialEq)]
+pub struct Traversal {
+    visited: Vec<(BagId, usize)>,
+}
+
+impl Traversal {
+    pub fn order(&self) -> Vec<BagId> {
+        self.visited.iter().map(|&(id, _)| id).collect()
+    }
+
+    pub fn set(&self) -> HashSet<BagId> {
+        self.visited.iter().map(|&(id, _)| id).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+}
+
+// Which part of the graph to export around the bag being queried.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Subgraph {
+    All,
+    Ancestors,
+    Descendants,
+}
+
+impl FromStr for Subgraph {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Subgraph::All),
+            "ancestors" => Ok(Subgraph::Ancestors),
+            "descendants" => Ok(Subgraph::Descendants),
+            _ => Err(format!(
+                "Unknown subgraph {:?}, expected all, ancestors or descendants",
+                s
+            )),
+        }
+    }
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum VisitState {
+    New,
+    OnStack,
+    Done,
+}
+
+// Every colour seen in the rules is interned to a `BagId` indexing into the
+// vectors below, with weighted edges kept in both directions.
+#[derive(Debug, Default)]
+pub struct BagIndex {
+    names: Vec<String>,
+    ids: HashMap<String, BagId>,
+    children: Vec<Vec<(u32, BagId)>>,
+    parents: Vec<Vec<(u32, BagId)>>,
+    // whether the bag had a rule of its own, rather than only being contained
+    defined: Vec<bool>,
+}
+
+impl BagIndex {
+    pub fn new() -> Self {
+        BagIndex {
+            ..Default::default()
+        }
+    }
+
+    pub fn intern(&mut self, name: &str) -> BagId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.children.push(vec![]);
+        self.parents.push(vec![]);
+        self.defined.push(false);
+        id
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    // Colours that had a rule of their own
+    pub fn rule_count(&self) -> usize {
+        self.defined.iter().filter(|&&defined| defined).count()
+    }
+
+    pub fn total_edges(&self) -> usize {
+        self.children.iter().map(|edges| edges.len()).sum()
+    }
+
+    pub fn get(&self, name: &str) -> Option<BagId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: BagId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn children(&self, id: BagId) -> &[(u32, BagId)] {
+        &self.children[id]
+    }
+
+    pub fn parents(&self, id: BagId) -> &[(u32, BagId)] {
+        &self.parents[id]
+    }
+
+    // Adds the edges from a rule, a repeated rule for the same bag only adds
+    // the children that weren't already listed.
+    pub fn insert_bag(&mut self, bag: &Bag) {
+        let parent = self.intern(&bag.name);
+        self.defined[parent] = true;
+
+        for (count, child_name) in bag.children.iter() {
+            let child = self.intern(child_name);
+            if self.children[parent].iter().any(|&(_, id)| id == child) {
+                continue;
+            }
+
+            self.children[parent].push((*count, child));
+            self.parents[child].push((*count, parent));
+        }
+    }
+
+    // Breadth first search over parent or child edges, visiting each bag
+    // once and stopping `max_depth` edges away from the start if given.
+    pub fn traverse(
+        &self,
+        start: BagId,
+        direction: Direction,
+        max_depth: Option<usize>,
+    ) -> Traversal {
+        let edges = match direction {
+            Direction::Parents => &self.parents,
+            Direction::Children => &self.children,
+        };
+        let mut seen = vec![false; self.names.len()];
+        let mut queue = VecDeque::new();
+        let mut traversal = Traversal::default();
+
+        seen[start] = true;
+        queue.push_back((start, 0));
+
+        while let Some((id, depth)) = queue.pop_front() {
+            if max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+
+            for &(_, next) in edges[id].iter() {
+                if !seen[next] {
+                    seen[next] = true;
+                    traversal.visited.push((next, depth + 1));
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+
+        traversal
+    }
+
+    pub fn ancestors(&self, id: BagId, max_depth: Option<usize>) -> Traversal {
+        self.traverse(id, Direction::Parents, max_depth)
+    }
+
+    pub fn descendants(&self, id: BagId, max_depth: Option<usize>) -> Traversal {
+        self.traverse(id, Direction::Children, max_depth)
+    }
+
+    pub fn count_parents(&self, name: &str) -> Option<usize> {
+        Some(self.ancestors(self.get(name)?, None).len())
+    }
+
+    // Which bags to include when exporting `subgraph` around `focus`, the
+    // focus itself is always included.
+    pub fn subgraph(&self, focus: BagId, subgraph: Subgraph) -> Vec<bool> {
+        let reached = match subgraph {
+            Subgraph::All => return vec![true; self.names.len()],
+            Subgraph::Ancestors => self.ancestors(focus, None).set(),
+            Subgraph::Descendants => self.descendants(focus, None).set(),
+        };
+        (0..self.names.len())
+            .map(|id| id == focus || reached.contains(&id))
+            .collect()
+    }
+
+    // Graphviz digraph with an edge from each bag to the bags it contains,
+    // labelled with the quantity, and the `focus` bag highlighted.
+    pub fn to_dot(&self, focus: Option<BagId>, include: &[bool]) -> String {
+        let mut out = String::new();
+        writeln!(out, "digraph bags {{").unwrap();
+        writeln!(out, "    rankdir=LR;").unwrap();
+        writeln!(out, "    node [shape=box];").unwrap();
+
+        for id in (0..self.names.len()).filter(|&id| include[id]) {
+            match focus {
+                Some(focus) if focus == id => writeln!(
+                    out,
+                    "    {} [style=filled, fillcolor=gold];",
+                    dot_id(self.name(id))
+                ),
+                _ => writeln!(out, "    {};", dot_id(self.name(id))),
+            }
+            .unwrap();
+        }
+
+        for id in (0..self.names.len()).filter(|&id| include[id]) {
+            for &(count, child) in self.children(id) {
+                if include[child] {
+                    writeln!(
+                        out,
+                        "    {} -> {} [label=\"{}\"];",
+                        dot_id(self.name(id)),
+                        dot_id(self.name(child)),
+                        count
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        writeln!(out, "}}").unwrap();
+        out
+    }
+
+    // Adjacency lists in both directions, keyed by colour:
+    // {"light red": {"contains": [{"count": 1, "bag": "bright white"}], "contained_in": []}}
+    pub fn to_json(&self, include: &[bool]) -> String {
+        let bags: Vec<String> = (0..self.names.len())
+            .filter(|&id| include[id])
+            .map(|id| {
+                let contains: Vec<String> = self
+                    .children(id)
+                    .iter()
+                    .filter(|&&(_, child)| include[child])
+                    .map(|&(count, child)| {
+                        format!(
+                            "{{\"count\": {}, \"bag\": {}}}",
+                            count,
+                            crate::json_string(self.name(child))
+                        )
+                    })
+                    .collect();
+                let contained_in: Vec<String> = self
+                    .parents(id)
+                    .iter()
+                    .filter(|&&(_, parent)| include[parent])
+                    .map(|&(_, parent)| crate::json_string(self.name(parent)))
+                    .collect();
+                format!(
+                    "  {}: {{\"contains\": [{}], \"contained_in\": [{}]}}",
+                    crate::json_string(self.name(id)),
+                    contains.join(", "),
+                    contained_in.join(", ")
+                )
+            })
+            .collect();
+
+        match bags.is_empty() {
+            true => "{}\n".to_string(),
+            false => format!("{{\n{}\n}}\n", bags.join(",\n")),
+        }
+    }
+
+    // Checks for bags that contain themselves, directly or through a cycle,
+    // which leave the totals undefined.
+    pub fn validate(&self) -> Result<(), Vec<BagGraphError>> {
+        let mut errors = vec![];
+
+        for id in 0..self.names.len() {
+            if self.children[id].iter().any(|&(_, child)| child == id) {
+                errors.push(BagGraphError::SelfLoop(self.name(id).to_string()));
+            }
+        }
+
+        for cycle in self.find_cycles() {
+            let path = cycle.iter().map(|&id| self.name(id).to_string()).collect();
+            errors.push(BagGraphError::Cycle(path));
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    // Bags that are contained somewhere but never get a rule. They are
+    // treated as holding nothing, so these are only worth a warning.
+    pub fn undefined(&self) -> Vec<BagGraphError> {
+        (0..self.names.len())
+            .filter(|&id| !self.defined[id])
+            .map(|id| {
+                let mut referenced_by: Vec<String> = self
+                    .parents(id)
+                    .iter()
+                    .map(|&(_, parent)| self.name(parent).to_string())
+                    .collect();
+                referenced_by.sort();
+                BagGraphError::Undefined {
+                    name: self.name(id).to_string(),
+                    referenced_by,
+                }
+            })
+            .collect()
+    }
+
+    // Cycles closed by the back edges of an iterative depth first search, as
+    // paths from a bag back to itself. Self-loops are left to `validate`.
+    fn find_cycles(&self) -> Vec<Vec<BagId>> {
+        let mut state = vec![VisitState::New; self.names.len()];
+        let mut cycles = vec![];
+
+        for root in 0..self.names.len() {
+            if state[root] != VisitState::New {
+                continue;
+            }
+
+            // (bag, index of the next child to visit)
+            let mut stack = vec![(root, 0)];
+            state[root] = VisitState::OnStack;
+
+            while let Some(&(id, next)) = stack.last() {
+                let child = match self.children[id].get(next) {
+                    Some(&(_, child)) => child,
+                    None => {
+                        state[id] = VisitState::Done;
+                        stack.pop();
+                        continue;
+                    }
+                };
+                stack.last_mut().unwrap().1 += 1;
+
+                match state[child] {
+                    VisitState::New => {
+                        state[child] = VisitState::OnStack;
+                        stack.push((child, 0));
+                    }
+                    VisitState::OnStack if child != id => {
+                        let start = stack.iter().position(|&(b, _)| b == child).unwrap();
+                        let mut cycle: Vec<BagId> =
+                            stack[start..].iter().map(|&(b, _)| b).collect();
+                        cycle.push(child);
+                        cycles.push(cycle);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        cycles
+    }
+
+    // Number of individual bags a `name` bag must contain, all the way down,
+    // or an error if there is no such bag or the total does not fit in a u64.
+    // Only meaningful once `validate` has ruled out cycles.
+    pub fn total_contained(&self, name: &str) -> Result<u64, String> {
+        let id = self
+            .get(name)
+            .ok_or_else(|| format!("No rule mentions a {} bag", name))?;
+        self.count_contained(id)
+            .ok_or_else(|| format!("{} bags hold more than {} bags", name, u64::MAX))
+    }
+
+    // Every bag reachable from `from`, each listed after all the bags it
+    // holds, by an iterative depth first search so that deep rule sets cannot
+    // overflow the stack. Only meaningful once `validate` has ruled out cycles.
+    fn post_order(&self, from: BagId) -> Vec<BagId> {
+        let mut seen = vec![false; self.names.len()];
+        let mut order = vec![];
+        let mut stack = vec![(from, 0)];
+        seen[from] = true;
+
+        while let Some(&(id, next)) = stack.last() {
+            match self.children[id].get(next) {
+                Some(&(_, child)) => {
+                    stack.last_mut().unwrap().1 += 1;
+                    if !seen[child] {
+                        seen[child] = true;
+                        stack.push((child, 0));
+                    }
+                }
+                None => {
+                    order.push(id);
+                    stack.pop();
+                }
+            }
+        }
+
+        order
+    }
+
+    // None once the count overflows
+    fn count_contained(&self, id: BagId) -> Option<u64> {
+        let mut totals: Vec<Option<u64>> = vec![None; self.names.len()];
+        for bag in self.post_order(id) {
+            totals[bag] = self.children[bag]
+                .iter()
+                .try_fold(0u64, |total, &(count, child)| {
+                    let bags = (count as u64).checked_mul(totals[child]?.checked_add(1)?)?;
+                    total.checked_add(bags)
+                });
+        }
+        totals[id]
+    }
+
+    // Fewest containment steps from `from` down to `to`, as the list of bags
+    // along the way including both ends.
+    pub fn shortest_chain(&self, from: BagId, to: BagId) -> Option<Vec<BagId>> {
+        let mut prev: Vec<Option<BagId>> = vec![None; self.names.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+
+        while let Some(id) = queue.pop_front() {
+            for &(_, child) in self.children(id) {
+                if child == from || prev[child].is_some() {
+                    continue;
+                }
+                prev[child] = Some(id);
+
+                if child == to {
+                    let mut chain = vec![to];
+                    while let Some(id) = prev[*chain.last().unwrap()] {
+                        chain.push(id);
+                    }
+                    chain.reverse();
+                    return Some(chain);
+                }
+                queue.push_back(child);
+            }
+        }
+
+        None
+    }
+
+    pub fn can_contain(&self, from: BagId, to: BagId) -> bool {
+        self.descendants(from, None).set().contains(&to)
+    }
+
+    pub fn edge_count(&self, from: BagId, to: BagId) -> Option<u32> {
+        self.children(from)
+            .iter()
+            .find(|&&(_, child)| child == to)
+            .map(|&(count, _)| count)
+    }
+
+    // Chain from `from` down to `to` holding the most bags, where a chain
+    // a -(2)-> b -(3)-> c holds 2 + 2 * 3 bags. Returns the chain and that
+    // total. Only meaningful once `validate` has ruled out cycles.
+    pub fn heaviest_chain(&self, from: BagId, to: BagId) -> Option<(Vec<BagId>, u64)> {
+        // the heaviest chain from each bag down to `to`, None if it has none
+        let mut weights: Vec<Option<u64>> = vec![None; self.names.len()];
+        let mut next = vec![None; self.names.len()];
+        for id in self.post_order(from) {
+            if id == to {
+                weights[id] = Some(0);
+                continue;
+            }
+            for &(count, child) in self.children[id].iter() {
+                if let Some(weight) = weights[child] {
+                    let total = (count as u64).saturating_mul(weight.saturating_add(1));
+                    if weights[id].is_none_or(|best| total > best) {
+                        weights[id] = Some(total);
+                        next[id] = Some(child);
+                    }
+                }
+            }
+        }
+
+        let mut chain = vec![from];
+        while let Some(id) = next[*chain.last().unwrap()] {
+            chain.push(id);
+        }
+        Some((chain, weights[from]?))
+    }
+
+    // Up to `limit` containment chains from `from` down to `to` that never
+    // visit the same bag twice, found by an iterative depth first search.
+    pub fn simple_paths(&self, from: BagId, to: BagId, limit: usize) -> Vec<Vec<BagId>> {
+        // only bags that can hold `to` are worth descending into
+        let leads_to = self.ancestors(to, None).set();
+        let mut on_path = vec![false; self.names.len()];
+        let mut stack = vec![(from, 0)];
+        let mut paths = vec![];
+        on_path[from] = true;
+
+        while let Some(&(id, next)) = stack.last() {
+            if paths.len() >= limit {
+                break;
+            }
+
+            let child = match self.children[id].get(next) {
+                Some(&(_, child)) => child,
+                None => {
+                    on_path[id] = false;
+                    stack.pop();
+                    continue;
+                }
+            };
+            stack.last_mut().unwrap().1 += 1;
+
+            if child == to {
+                let mut path: Vec<BagId> = stack.iter().map(|&(id, _)| id).collect();
+                path.push(to);
+                paths.push(path);
+            } else if !on_path[child] && leads_to.contains(&child) {
+                on_path[child] = true;
+                stack.push((child, 0));
+            }
+        }
+
+        paths
+    }
+
+    pub fn roots(&self) -> Vec<BagId> {
+        (0..self.names.len())
+            .filter(|&id| self.parents[id].is_empty())
+            .collect()
+    }
+
+    pub fn leaves(&self) -> Vec<BagId> {
+        (0..self.names.len())
+            .filter(|&id| self.children[id].is_empty())
+            .collect()
+    }
+
+    // Every bag listed after all the bags that can contain it (Kahn's
+    // algorithm), or `None` if the rules have a cycle.
+    pub fn topological_order(&self) -> Option<Vec<BagId>> {
+        let mut remaining: Vec<usize> = self.parents.iter().map(|edges| edges.len()).collect();
+        let mut queue: VecDeque<BagId> = self.roots().into_iter().collect();
+        let mut order = Vec::with_capacity(self.names.len());
+
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for &(_, child) in self.children(id) {
+                remaining[child] -= 1;
+                if remaining[child] == 0 {
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        match order.len() == self.names.len() {
+            true => Some(order),
+            false => None,
+        }
+    }
+
+    // For every bag, the longest chain of bags holding it down from a root,
+    // so roots are at depth 0 and every bag is deeper than its parents.
+    pub fn depths_from_roots(&self) -> Option<Vec<usize>> {
+        let mut depths = vec![0; self.names.len()];
+        for id in self.topological_order()? {
+            for &(_, child) in self.children(id) {
+                depths[child] = depths[child].max(depths[id] + 1);
+            }
+        }
+        Some(depths)
+    }
+
+    // Most levels of bags nested inside any one bag.
+    pub fn max_nesting_depth(&self) -> Option<usize> {
+        Some(self.depths_from_roots()?.into_iter().max().unwrap_or(0))
+    }
+
+    // How many levels of bags are nested inside `id`, 0 for a bag that holds
+    // nothing. Only meaningful once `validate` has ruled out cycles.
+    pub fn nesting_depth(&self, id: BagId) -> usize {
+        let mut depths = vec![0; self.names.len()];
+        for bag in self.post_order(id) {
+            depths[bag] = self.children[bag]
+                .iter()
+                .map(|&(_, child)| 1 + depths[child])
+                .max()
+                .unwrap_or(0);
+        }
+        depths[id]
+    }
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    const DAY: u8 = 7;
+    type Input = BagIndex;
+
+    // Loads every rule into a `BagIndex`, skipping blank lines, and rejects rule
+    // sets that fail validation. Bags without a rule are left for the caller
+    // to warn about via `undefined`.
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        let mut bags = BagIndex::new();
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let bag: Bag = line.parse()?;
+            bags.insert_bag(&bag);
+        }
+
+        bags.validate().map_err(|errors| {
+            errors
+                .iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        })?;
+        Ok(bags)
+    }
+
+    fn part1(bags: &Self::Input) -> Result<String, String> {
+        bags.count_parents("shiny gold")
+            .map(|count| count.to_string())
+            .ok_or_else(|| "No rule mentions a shiny gold bag".to_string())
+    }
+
+    fn part2(bags: &Self::Input) -> Result<String, String> {
+        bags.total_contained("shiny gold")
+            .map(|count| count.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    crate::example_tests!(Day7 {
+        part1,
+        part2,
+        part2_deep
+    });
+
+    const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+    const EXAMPLE_DEEP: &str = "\
+shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+    fn index_from_rules(rules: &str) -> BagIndex {
+        let mut bags = BagIndex::new();
+        for line in rules.lines() {
+            let bag: Bag = line.parse().unwrap();
+            bags.insert_bag(&bag);
+        }
+        bags
+    }
+
+    fn parent_names<'a>(bags: &'a BagIndex, name: &str) -> HashSet<&'a str> {
+        let id = bags.get(name).unwrap();
+        bags.parents(id)
+            .iter()
+            .map(|&(_, parent)| bags.name(parent))
+            .collect()
+    }
+
+    fn child_names<'a>(bags: &'a BagIndex, name: &str) -> Vec<(u32, &'a str)> {
+        let id = bags.get(name).unwrap();
+        bags.children(id)
+            .iter()
+            .map(|&(count, child)| (count, bags.name(child)))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_str() {
+        let test_str = "shiny tan bags contain 1 shiny gold bag.";
+        let expected_bag = Bag {
+            name: "shiny tan".to_string(),
+            children: vec![(1, "shiny gold".to_string())],
+        };
+
+        let bag: Bag = test_str.parse().unwrap();
+        assert_eq!(expected_bag, bag);
+    }
+
+    #[test]
+    fn test_from_str_counts() {
+        let bag: Bag = "light red bags contain 1 bright white bag, 2 muted yellow bags."
+            .parse()
+            .unwrap();
+        assert_eq!(bag.name, "light red");
+        assert_eq!(
+            bag.children,
+            vec![
+                (1, "bright white".to_string()),
+                (2, "muted yellow".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_str_no_other_bags() {
+        let bag: Bag = "faded blue bags contain no other bags.".parse().unwrap();
+        assert_eq!(bag.name, "faded blue");
+        assert!(bag.children.is_empty());
+    }
+
+    #[test]
+    fn test_from_str_malformed() {
+        let inputs = [
+            "",
+            "faded blue bags contain no other bags",
+            "faded blue bags hold 2 red bags.",
+            " bags contain 2 red bags.",
+            "faded blue bags contain two red bags.",
+            "faded blue bags contain 2 red boxes.",
+            "faded blue bags contain 2 bags.",
+            "faded blue bags contain 2 red bags,3 green bags.",
+        ];
+        for input in inputs.iter() {
+            assert!(
+                input.parse::<Bag>().is_err(),
+                "{:?} should not parse",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_insert_bag() {
+        let inputs = vec![
+            Bag {
+                name: "1".to_string(),
+                children: vec![(1, "2".to_string())],
+            },
+            Bag {
+                name: "2".to_string(),
+                children: vec![(1, "4".to_string()), (1, "3".to_string())],
+            },
+            Bag {
+                name: "3".to_string(),
+                children: vec![(1, "4".to_string())],
+            },
+        ];
+
+        let mut bags = BagIndex::new();
+
+        for bag in inputs {
+            bags.insert_bag(&bag);
+        }
+
+        for name in 1..=4 {
+            let (parents, children): (Vec<&str>, Vec<(u32, &str)>) = match name {
+                1 => (vec![], vec![(1, "2")]),
+                2 => (vec!["1"], vec![(1, "4"), (1, "3")]),
+                3 => (vec!["2"], vec![(1, "4")]),
+                4 => (vec!["2", "3"], vec![]),
+                _ => (vec![], vec![]),
+            };
+            let name = name.to_string();
+            assert_eq!(
+                parent_names(&bags, &name),
+                parents.into_iter().collect::<HashSet<&str>>()
+            );
+            assert_eq!(child_names(&bags, &name), children);
+        }
+
+        assert_eq!(bags.count_parents("4"), Some(3));
+        assert_eq!(bags.validate(), Ok(()));
+        assert_eq!(
+            bags.undefined(),
+            vec![BagGraphError::Undefined {
+                name: "4".to_string(),
+                referenced_by: vec!["2".to_string(), "3".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_insert_bag_repeated_rule() {
+        let mut bags = index_from_rules("a b bags contain 1 c d bag.");
+        bags.insert_bag(&"a b bags contain 2 c d bags, 3 e f bags.".parse().unwrap());
+
+        assert_eq!(child_names(&bags, "a b"), vec![(1, "c d"), (3, "e f")]);
+        assert_eq!(bags.parents(bags.get("c d").unwrap()).len(), 1);
+        assert_eq!(bags.count_parents("a b"), Some(0));
+    }
+
+    #[test]
+    fn test_example_count_parents() {
+        let bags = index_from_rules(EXAMPLE);
+        assert_eq!(bags.count_parents("shiny gold"), Some(4));
+        assert_eq!(bags.count_parents("no such"), None);
+    }
+
+    #[test]
+    fn test_total_contained() {
+        let bags = index_from_rules(EXAMPLE);
+        assert_eq!(bags.total_contained("shiny gold"), Ok(32));
+        assert_eq!(bags.total_contained("faded blue"), Ok(0));
+        assert_eq!(bags.total_contained("dark olive"), Ok(7));
+        assert!(bags.total_contained("no such").is_err());
+
+        let bags = index_from_rules(EXAMPLE_DEEP);
+        assert_eq!(bags.total_contained("shiny gold"), Ok(126));
+    }
+
+    #[test]
+    fn test_total_contained_overflow() {
+        // 999^7 is past u64::MAX, 999^6 is not
+        let level = |n: u8| match n {
+            0 => "shiny gold".to_string(),
+            n => format!("dark {}", (b'a' + n) as char),
+        };
+        let mut rules: Vec<String> = (0..7)
+            .map(|n| format!("{} bags contain 999 {} bags.", level(n), level(n + 1)))
+            .collect();
+        rules.push(format!("{} bags contain no other bags.", level(7)));
+        let bags = Day7::parse(&rules.join("\n")).unwrap();
+
+        assert!(bags.total_contained("dark b").is_ok());
+        assert_eq!(
+            Day7::part2(&bags),
+            Err(format!("shiny gold bags hold more than {} bags", u64::MAX))
+        );
+    }
+
+    #[test]
+    fn test_validate_example() {
+        assert_eq!(index_from_rules(EXAMPLE).validate(), Ok(()));
+        assert_eq!(index_from_rules(EXAMPLE_DEEP).validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_cycle() {
+        let bags = index_from_rules(
+            "a a bags contain 1 b b bag.
+b b bags contain 2 c c bags.
+c c bags contain 1 a a bag, 1 d d bag.
+d d bags contain no other bags.",
+        );
+        assert_eq!(
+            bags.validate(),
+            Err(vec![BagGraphError::Cycle(vec![
+                "a a".to_string(),
+                "b b".to_string(),
+                "c c".to_string(),
+                "a a".to_string(),
+            ])])
+        );
+    }
+
+    #[test]
+    fn test_validate_self_loop() {
+        let bags = index_from_rules(
+            "a a bags contain 1 a a bag, 1 b b bag.
+b b bags contain no other bags.",
+        );
+        assert_eq!(
+            bags.validate(),
+            Err(vec![BagGraphError::SelfLoop("a a".to_string())])
+        );
+        assert_eq!(
+            BagGraphError::SelfLoop("a a".to_string()).to_string(),
+            "a a bags contain themselves"
+        );
+    }
+
+    fn names(bags: &BagIndex, ids: Vec<BagId>) -> Vec<&str> {
+        ids.into_iter().map(|id| bags.name(id)).collect()
+    }
+
+    #[test]
+    fn test_traversal_order_and_depth() {
+        let bags = index_from_rules(EXAMPLE);
+        let shiny_gold = bags.get("shiny gold").unwrap();
+
+        let ancestors = bags.ancestors(shiny_gold, None);
+        assert_eq!(
+            names(&bags, ancestors.order()),
+            vec!["bright white", "muted yellow", "light red", "dark orange"]
+        );
+        assert_eq!(ancestors.len(), 4);
+        assert!(ancestors.set().contains(&bags.get("light red").unwrap()));
+
+        let near = bags.ancestors(shiny_gold, Some(1));
+        assert_eq!(
+            names(&bags, near.order()),
+            vec!["bright white", "muted yellow"]
+        );
+        assert_eq!(bags.ancestors(shiny_gold, Some(0)).len(), 0);
+
+        let descendants = bags.descendants(shiny_gold, None);
+        assert_eq!(
+            names(&bags, descendants.order()),
+            vec!["dark olive", "vibrant plum", "faded blue", "dotted black"]
+        );
+        assert_eq!(descendants.visited[2], (bags.get("faded blue").unwrap(), 2));
+    }
+
+    #[test]
+    fn test_traversal_diamonds() {
+        // 40 levels of two bags each, both holding both bags of the next
+        // level, so there are 2^40 distinct paths from top to bottom
+        let mut bags = BagIndex::new();
+        for level in 0..40 {
+            for side in &["left", "right"] {
+                bags.insert_bag(&Bag {
+                    name: format!("{} l{}", side, level),
+                    children: vec![
+                        (1, format!("left l{}", level + 1)),
+                        (1, format!("right l{}", level + 1)),
+                    ],
+                });
+            }
+        }
+
+        let bottom = bags.get("left l40").unwrap();
+        assert_eq!(bags.ancestors(bottom, None).len(), 80);
+        assert_eq!(
+            bags.descendants(bags.get("left l0").unwrap(), None).len(),
+            80
+        );
+    }
+
+    #[test]
+    fn test_traversal_deep_chain() {
+        let mut bags = BagIndex::new();
+        for level in 0..100_000 {
+            bags.insert_bag(&Bag {
+                name: format!("l{}", level),
+                children: vec![(1, format!("l{}", level + 1))],
+            });
+        }
+
+        assert_eq!(bags.count_parents("l100000"), Some(100_000));
+        assert_eq!(
+            bags.ancestors(bags.get("l100000").unwrap(), Some(10)).len(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_deep_chain_totals() {
+        // far deeper than the default stack allows recursion for
+        let mut bags = BagIndex::new();
+        bags.insert_bag(&Bag {
+            name: "shiny gold".to_string(),
+            children: vec![(1, "l0".to_string())],
+        });
+        for level in 0..200_000 {
+            bags.insert_bag(&Bag {
+                name: format!("l{}", level),
+                children: vec![(1, format!("l{}", level + 1))],
+            });
+        }
+        bags.insert_bag(&Bag {
+            name: "l200000".to_string(),
+            children: vec![],
+        });
+
+        assert_eq!(Day7::part2(&bags), Ok("200001".to_string()));
+        let shiny_gold = bags.get("shiny gold").unwrap();
+        assert_eq!(bags.nesting_depth(shiny_gold), 200_001);
+        assert_eq!(bags.max_nesting_depth(), Some(200_001));
+        let (chain, total) = bags
+            .heaviest_chain(shiny_gold, bags.get("l200000").unwrap())
+            .unwrap();
+        assert_eq!((chain.len(), total), (200_002, 200_001));
+    }
+
+    #[test]
+    fn test_traversal_cycle() {
+        let bags = index_from_rules(
+            "a a bags contain 1 b b bag.
+b b bags contain 1 a a bag.",
+        );
+        assert_eq!(bags.count_parents("a a"), Some(1));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let bags = index_from_rules(
+            "a a bags contain 1 b b bag, 2 c c bags.
+b b bags contain 3 c c bags.
+c c bags contain no other bags.",
+        );
+        let b = bags.get("b b").unwrap();
+
+        assert_eq!(
+            bags.to_dot(Some(b), &bags.subgraph(b, Subgraph::All)),
+            "digraph bags {
+    rankdir=LR;
+    node [shape=box];
+    \"a a\";
+    \"b b\" [style=filled, fillcolor=gold];
+    \"c c\";
+    \"a a\" -> \"b b\" [label=\"1\"];
+    \"a a\" -> \"c c\" [label=\"2\"];
+    \"b b\" -> \"c c\" [label=\"3\"];
+}
+"
+        );
+
+        let dot = bags.to_dot(Some(b), &bags.subgraph(b, Subgraph::Descendants));
+        assert!(!dot.contains("\"a a\""));
+        assert!(dot.contains("\"b b\" -> \"c c\" [label=\"3\"];"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let bags = index_from_rules(
+            "a a bags contain 1 b b bag, 2 c c bags.
+b b bags contain 3 c c bags.
+c c bags contain no other bags.",
+        );
+        let b = bags.get("b b").unwrap();
+
+        assert_eq!(
+            bags.to_json(&bags.subgraph(b, Subgraph::Ancestors)),
+            "{
+  \"a a\": {\"contains\": [{\"count\": 1, \"bag\": \"b b\"}], \"contained_in\": []},
+  \"b b\": {\"contains\": [], \"contained_in\": [\"a a\"]}
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_subgraph_from_str() {
+        assert_eq!("ancestors".parse(), Ok(Subgraph::Ancestors));
+        assert!("parents".parse::<Subgraph>().is_err());
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let bags = index_from_rules(EXAMPLE);
+        let order = bags.topological_order().unwrap();
+        assert_eq!(order.len(), 9);
+
+        let position: HashMap<BagId, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(pos, &id)| (id, pos))
+            .collect();
+        for id in 0..bags.names.len() {
+            for &(_, child) in bags.children(id) {
+                assert!(position[&id] < position[&child]);
+            }
+        }
+
+        let cyclic = index_from_rules(
+            "a a bags contain 1 b b bag.
+b b bags contain 1 a a bag.",
+        );
+        assert_eq!(cyclic.topological_order(), None);
+        assert_eq!(cyclic.max_nesting_depth(), None);
+    }
+
+    #[test]
+    fn test_roots_leaves_and_depths() {
+        let bags = index_from_rules(EXAMPLE);
+        assert_eq!(names(&bags, bags.roots()), vec!["light red", "dark orange"]);
+        assert_eq!(
+            names(&bags, bags.leaves()),
+            vec!["faded blue", "dotted black"]
+        );
+
+        let depths = bags.depths_from_roots().unwrap();
+        let depth = |name: &str| depths[bags.get(name).unwrap()];
+        assert_eq!(depth("light red"), 0);
+        assert_eq!(depth("muted yellow"), 1);
+        assert_eq!(depth("shiny gold"), 2);
+        // reachable from muted yellow in one step, but through shiny gold in three
+        assert_eq!(depth("faded blue"), 4);
+        assert_eq!(bags.max_nesting_depth(), Some(4));
+
+        let bags = index_from_rules(EXAMPLE_DEEP);
+        assert_eq!(bags.max_nesting_depth(), Some(6));
+    }
+
+    #[test]
+    fn test_parse_rejects_cycles() {
+        assert!(Day7::parse("a a bags contain 1 a a bag.").is_err());
+
+        // a bag without a rule is only a warning and holds nothing
+        let bags = Day7::parse("shiny gold bags contain 2 dark red bags.").unwrap();
+        assert_eq!(bags.undefined().len(), 1);
+        assert_eq!(Day7::part2(&bags), Ok("2".to_string()));
+
+        let bags = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part1(&bags), Ok("4".to_string()));
+        assert_eq!(Day7::part2(&bags), Ok("32".to_string()));
+    }
+}
@@ -0,0 +1,61 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+
+use crate::bench::{self, Timings};
+use crate::solver::Timed;
+use crate::Solver;
+
+// Solves one part of a puzzle from the full text of its input
+pub type PartFn = fn(&str) -> Result<String, String>;
+// Parses once and solves both parts, timing each, or fails to parse
+pub type BothFn = fn(&str) -> Result<[Timed; 2], String>;
+// Times the parse and solve phases over the given number of iterations
+pub type BenchFn = fn(&str, usize) -> Result<Timings, String>;
+
+pub struct Puzzle {
+    pub day: u8,
+    pub part1: PartFn,
+    pub part2: PartFn,
+    pub both: BothFn,
+    pub bench: BenchFn,
+}
+
+impl Puzzle {
+    pub const fn of<S: Solver>() -> Self {
+        Puzzle {
+            day: S::DAY,
+            part1: |input| S::solve(input, 1),
+            part2: |input| S::solve(input, 2),
+            both: S::solve_both,
+            bench: bench::measure::<S>,
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<PartFn> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+}
+
+// Every solved day, in order
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::of::<day1::Day1>(),
+    Puzzle::of::<day2::Day2>(),
+    Puzzle::of::<day3::Day3>(),
+    Puzzle::of::<day4::Day4>(),
+    Puzzle::of::<day5::Day5>(),
+    Puzzle::of::<day6::Day6>(),
+    Puzzle::of::<day7::Day7>(),
+];
+
+pub fn find(day: u8) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day == day)
+}
@@ -0,0 +1,170 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Solver;
+
+// Worked examples from the puzzle text live under `examples/dayN/`, one
+// `<name>.txt` input and `<name>.answer` expected answer per example. The
+// name starts with the part it checks, `part1`, `part2` or e.g.
+// `part2_deep` for a second example of the same part. An empty answer file
+// marks an example whose answer is not filled in yet.
+pub const EXAMPLES_DIR: &str = "examples";
+
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub day: u8,
+    pub part: u8,
+    pub name: String,
+    pub input: String,
+    // None until the answer file has something in it
+    pub answer: Option<String>,
+}
+
+pub fn examples_dir(root: &Path, day: u8) -> PathBuf {
+    root.join(EXAMPLES_DIR).join(format!("day{}", day))
+}
+
+pub fn part_of(name: &str) -> Option<u8> {
+    match name.strip_prefix("part")?.split('_').next()? {
+        "1" => Some(1),
+        "2" => Some(2),
+        _ => None,
+    }
+}
+
+pub fn load(root: &Path, day: u8, name: &str) -> Result<Example, String> {
+    let part = part_of(name).ok_or_else(|| format!("Example {:?} names no part", name))?;
+    let dir = examples_dir(root, day);
+    let read = |extension: &str| {
+        let path = dir.join(format!("{}.{}", name, extension));
+        fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))
+    };
+
+    let answer = read("answer")?.trim().to_string();
+    Ok(Example {
+        day,
+        part,
+        name: name.to_string(),
+        input: read("txt")?,
+        answer: Some(answer).filter(|answer| !answer.is_empty()),
+    })
+}
+
+// Names of every `.txt` fixture for `day`, sorted. Names that do not start
+// with a part are included so that `load` can report them.
+pub fn list(root: &Path, day: u8) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(examples_dir(root, day))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+// Runs `S` on the named example from this repository's fixtures, panicking
+// with the difference if the answer is wrong or missing.
+pub fn check<S: Solver>(name: &str) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let example = load(root, S::DAY, name).unwrap();
+    let expected = example.answer.unwrap_or_else(|| {
+        panic!(
+            "day {} example {} has no answer in {}.answer",
+            S::DAY,
+            name,
+            examples_dir(root, S::DAY).join(name).display()
+        )
+    });
+
+    assert_eq!(
+        S::solve(&example.input, example.part),
+        Ok(expected),
+        "day {} example {}",
+        S::DAY,
+        name
+    );
+}
+
+// Panics unless `declared` names every fixture in `examples/dayN/`, so a new
+// fixture cannot go untested.
+pub fn check_declared(day: u8, declared: &[&str]) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut declared = declared.to_vec();
+    declared.sort_unstable();
+    assert_eq!(
+        list(root, day),
+        declared,
+        "examples/day{} does not match the names given to example_tests!",
+        day
+    );
+}
+
+// Generates one test per named fixture in `examples/dayN/` for a solver, and
+// one checking that no fixture was left out. A fixture whose answer is not
+// known yet can be marked `#[ignore]`:
+//
+//     example_tests!(Day7 { part1, part2, #[ignore] part2_deep });
+#[macro_export]
+macro_rules! example_tests {
+    ($solver:ty { $($(#[$attr:meta])* $name:ident),* $(,)? }) => {
+        mod examples {
+            use super::*;
+
+            $(
+                $(#[$attr])*
+                #[test]
+                fn $name() {
+                    $crate::examples::check::<$solver>(stringify!($name));
+                }
+            )*
+
+            #[test]
+            fn every_fixture_declared() {
+                $crate::examples::check_declared(
+                    <$solver as $crate::Solver>::DAY,
+                    &[$(stringify!($name)),*],
+                );
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part_of() {
+        assert_eq!(part_of("part1"), Some(1));
+        assert_eq!(part_of("part2_deep"), Some(2));
+        assert_eq!(part_of("part3"), None);
+        assert_eq!(part_of("part12"), None);
+        assert_eq!(part_of("example"), None);
+    }
+
+    #[test]
+    fn test_list_and_load() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(list(root, 7), vec!["part1", "part2", "part2_deep"]);
+        assert!(list(root, 25).is_empty());
+
+        let example = load(root, 7, "part2_deep").unwrap();
+        assert_eq!(example.part, 2);
+        assert_eq!(example.answer, Some("126".to_string()));
+        assert!(load(root, 7, "part3").is_err());
+        assert!(load(root, 7, "part1_missing").is_err());
+        assert!(load(root, 7, "sample").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match")]
+    fn test_check_declared_missing_fixture() {
+        check_declared(7, &["part1", "part2"]);
+    }
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::http::{Client, HttpError, YEAR};
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    // the input was already on disk, nothing was requested
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+pub fn input_path(inputs_dir: &Path, day: u8) -> PathBuf {
+    inputs_dir.join(format!("day{}.txt", day))
+}
+
+// Downloads a day's input into `inputs_dir/dayN.txt`, unless a non-empty copy
+// is already there and `force` is not set.
+pub fn fetch_input(
+    client: &Client,
+    day: u8,
+    inputs_dir: &Path,
+    force: bool,
+) -> Result<Fetched, String> {
+    let path = input_path(inputs_dir, day);
+    let cached = fs::metadata(&path).is_ok_and(|meta| meta.len() > 0);
+    if cached && !force {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client
+        .get(&format!("/{}/day/{}/input", YEAR, day))
+        .map_err(|err| match err {
+            HttpError::Status(404, _) => format!("Day {} is not available yet", day),
+            HttpError::Status(400, _) | HttpError::Status(500, _) => {
+                format!("Fetching day {} failed, is the session token valid?", day)
+            }
+            HttpError::Status(code, body) => {
+                format!(
+                    "Fetching day {} failed with HTTP {}: {}",
+                    day,
+                    code,
+                    body.trim()
+                )
+            }
+            HttpError::Other(err) => format!("Fetching day {} failed: {}", day, err),
+        })?;
+
+    fs::create_dir_all(inputs_dir)
+        .and_then(|_| fs::write(&path, input))
+        .map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+    Ok(Fetched::Downloaded(path))
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+
+pub const YEAR: u16 = 2020;
+const USER_AGENT: &str = "github.com/lbrindze/AdventOfCode2020 aoc tool";
+const LAST_REQUEST: &str = "last-request";
+
+// An HTTP error status along with whatever the server sent back
+#[derive(Debug, PartialEq)]
+pub enum HttpError {
+    Status(u16, String),
+    Other(String),
+}
+
+// Sends authenticated requests to the puzzle site, never more often than the
+// configured minimum interval. The time of the last request is kept under
+// `state_dir` so the limit also holds across separate runs of `aoc`.
+pub struct Client<'a> {
+    config: &'a Config,
+    agent: ureq::Agent,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl<'a> Client<'a> {
+    pub fn new(config: &'a Config) -> Self {
+        Client {
+            config,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+    }
+
+    // Sleeps until `min_interval` has passed since the previous request.
+    pub fn throttle(&self) -> Result<(), String> {
+        let path = self.config.state_dir.join(LAST_REQUEST);
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok());
+        if let Some(last) = last {
+            let wait =
+                (last + self.config.min_interval.as_millis() as u64).saturating_sub(now_ms());
+            if wait > 0 {
+                thread::sleep(Duration::from_millis(wait));
+            }
+        }
+
+        fs::create_dir_all(&self.config.state_dir)
+            .and_then(|_| fs::write(&path, now_ms().to_string()))
+            .map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    }
+
+    fn send(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, HttpError> {
+        let session = self.config.session().map_err(HttpError::Other)?;
+        self.throttle().map_err(HttpError::Other)?;
+        let request = request.set("Cookie", &format!("session={}", session));
+        let response = match form {
+            Some(fields) => request.send_form(fields),
+            None => request.call(),
+        };
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| HttpError::Other(e.to_string())),
+            Err(ureq::Error::Status(code, response)) => Err(HttpError::Status(
+                code,
+                response.into_string().unwrap_or_default(),
+            )),
+            Err(err) => Err(HttpError::Other(err.to_string())),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, HttpError> {
+        self.send(self.agent.get(&self.url(path)), None)
+    }
+
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String, HttpError> {
+        self.send(self.agent.post(&self.url(path)), Some(fields))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::TempDir;
+    use std::time::Instant;
+
+    #[test]
+    fn test_throttle() {
+        let dir = TempDir::new("throttle");
+        let config = Config {
+            min_interval: Duration::from_millis(200),
+            state_dir: dir.to_path_buf(),
+            ..Config::default()
+        };
+        let client = Client::new(&config);
+
+        let start = Instant::now();
+        client.throttle().unwrap();
+        client.throttle().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(
+            client.url("/2020/day/1/input"),
+            "https://adventofcode.com/2020/day/1/input"
+        );
+    }
+}
@@ -4,6 +4,24 @@ use std::io::Split;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 
+pub mod answers;
+pub mod bench;
+pub mod config;
+pub mod days;
+pub mod examples;
+pub mod fetch;
+pub mod http;
+pub mod report;
+pub mod scaffold;
+pub mod solver;
+pub mod submit;
+pub mod testing;
+mod toml;
+pub mod verify;
+pub mod watch;
+
+pub use solver::Solver;
+
 pub type InputResult = io::Result<InputLines>;
 pub type InputLines = io::Lines<io::BufReader<File>>;
 
@@ -33,3 +51,22 @@ where
     let reader = io::BufReader::new(file);
     Ok(reader.split(c))
 }
+
+// Quote and escape a string for hand written JSON output
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
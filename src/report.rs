@@ -0,0 +1,479 @@
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::days::Puzzle;
+use crate::fetch;
+use crate::json_string;
+use crate::Solver;
+
+// How results are printed: free-form text for people, or one JSON object per
+// line for anything that wants to parse them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Human,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format {:?}, expected human or json", s)),
+        }
+    }
+}
+
+pub fn elapsed_ms(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1000.0
+}
+
+// A JSON object from keys and already encoded values, on a single line
+pub fn json_object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}: {}", json_string(key), value))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+pub fn json_ms(elapsed: Duration) -> String {
+    format!("{:.3}", elapsed_ms(elapsed))
+}
+
+// The outcome of running one part of one day against an input file
+#[derive(Debug, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+    pub input_path: PathBuf,
+}
+
+impl Report {
+    // {"day": 1, "part": 1, "answer": "299299", "elapsed_ms": 0.118, "input_path": "inputs/day1.txt"}
+    // with "error" in place of "answer" when the part failed.
+    pub fn to_json(&self) -> String {
+        let (key, value) = match &self.answer {
+            Ok(answer) => ("answer", answer),
+            Err(err) => ("error", err),
+        };
+        json_object(&[
+            ("day", self.day.to_string()),
+            ("part", self.part.to_string()),
+            (key, json_string(value)),
+            ("elapsed_ms", json_ms(self.elapsed)),
+            (
+                "input_path",
+                json_string(&self.input_path.display().to_string()),
+            ),
+        ])
+    }
+
+    pub fn to_human(&self) -> String {
+        match &self.answer {
+            Ok(answer) => format!(
+                "Day {} part {}: {} ({:.3} ms)",
+                self.day,
+                self.part,
+                answer,
+                elapsed_ms(self.elapsed)
+            ),
+            Err(err) => format!("Day {} part {} failed: {}", self.day, self.part, err),
+        }
+    }
+
+    pub fn format(&self, format: Format) -> String {
+        match format {
+            Format::Human => self.to_human(),
+            Format::Json => self.to_json(),
+        }
+    }
+}
+
+// An error that is not tied to a single part, e.g. a bad command line
+pub fn error_json(message: &str) -> String {
+    json_object(&[("error", json_string(message))])
+}
+
+// Times `solve` on the contents of `input_path`, reporting a missing or
+// unreadable file as that part's error.
+fn report(
+    day: u8,
+    part: u8,
+    input_path: &Path,
+    solve: impl Fn(&str) -> Result<String, String>,
+) -> Report {
+    let (answer, elapsed) = match fs::read_to_string(input_path) {
+        Ok(input) => {
+            let start = Instant::now();
+            let answer = solve(&input);
+            (answer, start.elapsed())
+        }
+        Err(e) => (
+            Err(format!("{}: {}", input_path.display(), e)),
+            Duration::default(),
+        ),
+    };
+    Report {
+        day,
+        part,
+        answer,
+        elapsed,
+        input_path: input_path.to_path_buf(),
+    }
+}
+
+pub fn run(puzzle: &Puzzle, part: u8, input_path: &Path) -> Report {
+    report(puzzle.day, part, input_path, |input| {
+        match puzzle.part(part) {
+            Some(solve) => solve(input),
+            None => Err(format!("Day {} has no part {}", puzzle.day, part)),
+        }
+    })
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+// Both parts of one day from a single read and parse of its input. An
+// unreadable input, a parse error or a panic is reported against both parts.
+fn run_both(puzzle: &Puzzle, input_path: &Path) -> Vec<Report> {
+    let answers = fs::read_to_string(input_path)
+        .map_err(|e| format!("{}: {}", input_path.display(), e))
+        .and_then(|input| {
+            panic::catch_unwind(AssertUnwindSafe(|| (puzzle.both)(&input)))
+                .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(&*payload))))
+        });
+    let answers = match answers {
+        Ok(answers) => answers,
+        Err(err) => [
+            (Err(err.clone()), Duration::default()),
+            (Err(err), Duration::default()),
+        ],
+    };
+    (1..)
+        .zip(answers)
+        .map(|(part, (answer, elapsed))| Report {
+            day: puzzle.day,
+            part,
+            answer,
+            elapsed,
+            input_path: input_path.to_path_buf(),
+        })
+        .collect()
+}
+
+// Runs both parts of every puzzle on `threads` worker threads, each taking the
+// next day until none are left, so its input is only parsed once. A day that
+// fails or panics only spoils its own reports. Reports come back in the order
+// of `puzzles`.
+pub fn run_all(puzzles: &[Puzzle], inputs_dir: &Path, threads: usize) -> Vec<Report> {
+    let next = AtomicUsize::new(0);
+
+    let mut done: Vec<(usize, Vec<Report>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.clamp(1, puzzles.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let puzzle = match puzzles.get(idx) {
+                            Some(puzzle) => puzzle,
+                            None => return done,
+                        };
+                        let input_path = fetch::input_path(inputs_dir, puzzle.day);
+                        done.push((idx, run_both(puzzle, &input_path)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+
+    done.sort_by_key(|(idx, _)| *idx);
+    done.into_iter().flat_map(|(_, reports)| reports).collect()
+}
+
+// A table of every report with the summed solve time, e.g.
+//
+//     day  part  answer      time
+//       1     1  299299      0.118 ms
+//       1     2  287730716   5.211 ms
+//     total                  5.329 ms
+pub fn summary_table(reports: &[Report]) -> String {
+    let answers: Vec<String> = reports
+        .iter()
+        .map(|report| match &report.answer {
+            Ok(answer) => answer.clone(),
+            Err(err) => format!("error: {}", err.lines().next().unwrap_or_default()),
+        })
+        .collect();
+    let width = answers.iter().map(|a| a.len()).max().unwrap_or(0).max(6);
+
+    let mut table = format!("day  part  {:<width$}  time\n", "answer", width = width);
+    for (report, answer) in reports.iter().zip(answers.iter()) {
+        table += &format!(
+            "{:>3}  {:>4}  {:<width$}  {:.3} ms\n",
+            report.day,
+            report.part,
+            answer,
+            elapsed_ms(report.elapsed),
+            width = width
+        );
+    }
+    let total: Duration = reports.iter().map(|report| report.elapsed).sum();
+    table += &format!(
+        "{:<width$}  {:.3} ms",
+        "total",
+        elapsed_ms(total),
+        width = width + 11
+    );
+    table
+}
+
+// Takes `--format <format>` out of a binary's arguments, returning the
+// format and every other argument in order.
+pub fn split_format(args: impl Iterator<Item = String>) -> Result<(Format, Vec<String>), String> {
+    let mut args = args;
+    let mut format = Format::Human;
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = args.next().ok_or("--format needs a value")?.parse()?,
+            _ => rest.push(arg),
+        }
+    }
+    Ok((format, rest))
+}
+
+// The input file when the dayN binaries print JSON. The JSON is always both
+// parts' answers, so any other argument (a slope, a layout, a colour) would
+// be silently ignored and is rejected instead.
+pub fn json_input(args: &[String]) -> Result<PathBuf, String> {
+    match args {
+        [] => Err("Missing input file".to_string()),
+        [path] => Ok(PathBuf::from(path)),
+        [_, extra @ ..] => Err(format!(
+            "--format json only reports both parts for the input file, remove {}",
+            extra.join(" ")
+        )),
+    }
+}
+
+// For the dayN binaries: when `--format json` is on the command line, print
+// both parts for the input file given as the only other argument and exit.
+// Otherwise returns the arguments without `--format`, for the binary's own
+// output in the default human format.
+pub fn run_if_json<S: Solver>() -> Vec<String> {
+    let (format, args) = match split_format(env::args().skip(1)) {
+        Ok(split) => split,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
+    if format != Format::Json {
+        return args;
+    }
+
+    let path = match json_input(&args) {
+        Ok(path) => path,
+        Err(err) => {
+            println!("{}", error_json(&err));
+            process::exit(2);
+        }
+    };
+
+    let mut failed = false;
+    for part in 1..=2 {
+        let report = report(S::DAY, part, &path, |input| S::solve(input, part));
+        failed |= report.answer.is_err();
+        println!("{}", report.to_json());
+    }
+    process::exit(failed as i32);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    fn test_to_json() {
+        let mut report = Report {
+            day: 3,
+            part: 2,
+            answer: Ok("3584591857".to_string()),
+            elapsed: Duration::from_micros(1250),
+            input_path: PathBuf::from("inputs/day3.txt"),
+        };
+        assert_eq!(
+            report.to_json(),
+            "{\"day\": 3, \"part\": 2, \"answer\": \"3584591857\", \"elapsed_ms\": 1.250, \"input_path\": \"inputs/day3.txt\"}"
+        );
+        assert_eq!(
+            report.format(Format::Human),
+            "Day 3 part 2: 3584591857 (1.250 ms)"
+        );
+
+        report.answer = Err("No \"slope\"".to_string());
+        assert_eq!(
+            report.to_json(),
+            "{\"day\": 3, \"part\": 2, \"error\": \"No \\\"slope\\\"\", \"elapsed_ms\": 1.250, \"input_path\": \"inputs/day3.txt\"}"
+        );
+        assert_eq!(error_json("bad\nflag"), "{\"error\": \"bad\\nflag\"}");
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_split_format() {
+        let args = |args: &[&str]| split_format(args.iter().map(|arg| arg.to_string()));
+        let rest = |rest: &[&str]| rest.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            args(&["--format", "human", "inputs/day1.txt", "2"]),
+            Ok((Format::Human, rest(&["inputs/day1.txt", "2"])))
+        );
+        assert_eq!(
+            args(&["inputs/day7.txt", "--format", "json", "--depth", "2"]),
+            Ok((Format::Json, rest(&["inputs/day7.txt", "--depth", "2"])))
+        );
+        assert_eq!(
+            args(&["inputs/day1.txt"]),
+            Ok((Format::Human, rest(&["inputs/day1.txt"])))
+        );
+        assert!(args(&["inputs/day1.txt", "--format"]).is_err());
+        assert!(args(&["--format", "yaml"]).is_err());
+    }
+
+    #[test]
+    fn test_json_input() {
+        let args =
+            |args: &[&str]| json_input(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>());
+
+        assert_eq!(
+            args(&["inputs/day3.txt"]),
+            Ok(PathBuf::from("inputs/day3.txt"))
+        );
+        assert_eq!(args(&[]), Err("Missing input file".to_string()));
+        assert_eq!(
+            args(&["inputs/day3.txt", "1", "2"]),
+            Err("--format json only reports both parts for the input file, remove 1 2".to_string())
+        );
+        assert!(args(&["inputs/day7.txt", "dark", "red"]).is_err());
+        assert!(args(&["inputs/day5.txt", "256", "16"]).is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let puzzle = crate::days::find(1).unwrap();
+        let report = run(puzzle, 1, Path::new("no/such/input.txt"));
+        assert!(report.answer.unwrap_err().starts_with("no/such/input.txt"));
+
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let report = run(puzzle, 1, &root.join("examples/day1/part1.txt"));
+        assert_eq!(report.answer, Ok("514579".to_string()));
+        assert!(run(puzzle, 3, &root.join("examples/day1/part1.txt"))
+            .answer
+            .is_err());
+    }
+
+    #[test]
+    fn test_run_all() {
+        static PARSED: AtomicUsize = AtomicUsize::new(0);
+        let unused = |_: &str| Err("unused".to_string());
+        let puzzles = [
+            Puzzle {
+                day: 2,
+                part1: unused,
+                part2: unused,
+                both: |_| panic!("out of bounds"),
+                bench: |_, _| Err("unused".to_string()),
+            },
+            Puzzle {
+                day: 1,
+                part1: unused,
+                part2: unused,
+                both: |input| {
+                    PARSED.fetch_add(1, Ordering::Relaxed);
+                    Ok([
+                        (Ok(input.trim().to_string()), Duration::default()),
+                        (Err("not\nsolved".to_string()), Duration::default()),
+                    ])
+                },
+                bench: |_, _| Err("unused".to_string()),
+            },
+            Puzzle {
+                day: 3,
+                part1: unused,
+                part2: unused,
+                both: |_| Err("bad input".to_string()),
+                bench: |_, _| Err("unused".to_string()),
+            },
+        ];
+        let dir = TempDir::new("run_all");
+        fs::write(fetch::input_path(&dir, 1), "one\n").unwrap();
+        fs::write(fetch::input_path(&dir, 2), "").unwrap();
+        fs::write(fetch::input_path(&dir, 3), "").unwrap();
+
+        let answers = |threads| {
+            run_all(&puzzles, &dir, threads)
+                .into_iter()
+                .map(|r| (r.day, r.part, r.answer))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(answers(1), answers(8));
+        assert_eq!(
+            answers(3),
+            vec![
+                (2, 1, Err("panicked: out of bounds".to_string())),
+                (2, 2, Err("panicked: out of bounds".to_string())),
+                (1, 1, Ok("one".to_string())),
+                (1, 2, Err("not\nsolved".to_string())),
+                (3, 1, Err("bad input".to_string())),
+                (3, 2, Err("bad input".to_string())),
+            ]
+        );
+        // once per day for each of the three runs, not once per part
+        assert_eq!(PARSED.load(Ordering::Relaxed), 3);
+
+        fs::remove_file(fetch::input_path(&dir, 3)).unwrap();
+        let missing = run_all(&puzzles[2..], &dir, 1);
+        assert_eq!(missing.len(), 2);
+        assert!(missing
+            .iter()
+            .all(|r| r.answer.as_ref().unwrap_err().contains("day3.txt")));
+
+        let table = summary_table(&[Report {
+            elapsed: Duration::from_micros(1250),
+            ..run_all(&puzzles[1..2], &dir, 1).remove(1)
+        }]);
+        assert_eq!(
+            table,
+            "day  part  answer      time
+  1     2  error: not  1.250 ms
+total                  1.250 ms"
+        );
+    }
+}
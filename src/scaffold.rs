@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::examples::examples_dir;
+
+// Starting point for a new day, `{day}` is replaced with the day number.
+const TEMPLATE: &str = r#"use crate::Solver;
+
+pub struct Day{day};
+
+impl Solver for Day{day} {
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, String> {
+        Ok(input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn part1(_input: &Self::Input) -> Result<String, String> {
+        Err("Day {day} part 1 is not solved yet".to_string())
+    }
+
+    fn part2(_input: &Self::Input) -> Result<String, String> {
+        Err("Day {day} part 2 is not solved yet".to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // drop the #[ignore] once examples/day{day}/partN.answer is filled in
+    crate::example_tests!(Day{day} {
+        #[ignore = "no answer in examples/day{day}/part1.answer yet"]
+        part1,
+        #[ignore = "no answer in examples/day{day}/part2.answer yet"]
+        part2,
+    });
+}
+"#;
+
+fn module_path(root: &Path, day: u8) -> PathBuf {
+    root.join("src").join("days").join(format!("day{}.rs", day))
+}
+
+fn registry_path(root: &Path) -> PathBuf {
+    root.join("src").join("days").join("mod.rs")
+}
+
+fn day_of(line: &str, prefix: &str, suffix: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix(prefix)?
+        .strip_suffix(suffix)?
+        .parse()
+        .ok()
+}
+
+// Adds `pub mod dayN;` and the day's `PUZZLES` entry to the registry source,
+// each after the last earlier day so both lists stay in order.
+pub fn register(registry: &str, day: u8) -> Result<String, String> {
+    let mut lines: Vec<String> = registry.lines().map(str::to_string).collect();
+    if lines
+        .iter()
+        .any(|line| day_of(line, "pub mod day", ";") == Some(day))
+    {
+        return Err(format!("Day {} is already registered", day));
+    }
+
+    let entry = |line: &str| {
+        line.trim()
+            .strip_prefix("Puzzle::of::<day")
+            .and_then(|rest| rest.split_once("::"))
+            .and_then(|(n, _)| n.parse::<u8>().ok())
+    };
+    let table = lines
+        .iter()
+        .position(|line| line.starts_with("pub const PUZZLES"))
+        .ok_or("No PUZZLES table in the registry")?;
+    let table_end = table
+        + lines[table..]
+            .iter()
+            .position(|line| line.trim() == "];")
+            .ok_or("Unterminated PUZZLES table")?;
+    let at = (table + 1..table_end)
+        .find(|&idx| entry(&lines[idx]).is_some_and(|n| n > day))
+        .unwrap_or(table_end);
+    lines.insert(at, format!("    Puzzle::of::<day{}::Day{}>(),", day, day));
+
+    let modules: Vec<usize> = (0..lines.len())
+        .filter(|&idx| day_of(&lines[idx], "pub mod day", ";").is_some())
+        .collect();
+    let at = modules
+        .iter()
+        .find(|&&idx| day_of(&lines[idx], "pub mod day", ";").unwrap() > day)
+        .copied()
+        .or_else(|| modules.last().map(|&idx| idx + 1))
+        .unwrap_or(0);
+    lines.insert(at, format!("pub mod day{};", day));
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Creates the module, its registration, an empty input and empty example
+// fixtures for `day` under the repository at `root`, returning every file
+// written. Nothing is written if any of the new files already exists.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There is no day {}", day));
+    }
+
+    // the module first, then files that start out empty
+    let module = module_path(root, day);
+    let mut created = vec![
+        module.clone(),
+        root.join("inputs").join(format!("day{}.txt", day)),
+    ];
+    for file in ["part1.txt", "part1.answer", "part2.txt", "part2.answer"].iter() {
+        created.push(examples_dir(root, day).join(file));
+    }
+    if let Some(path) = created.iter().find(|path| path.exists()) {
+        return Err(format!("{} already exists", path.display()));
+    }
+
+    let registry = registry_path(root);
+    let source = fs::read_to_string(&registry)
+        .map_err(|e| format!("Could not read {}: {}", registry.display(), e))?;
+    let source = register(&source, day)?;
+
+    let write = |path: &Path, contents: &str| {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(path, contents))
+            .map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    };
+    write(&module, &TEMPLATE.replace("{day}", &day.to_string()))?;
+    write(&registry, &source)?;
+    for path in created.iter().skip(1) {
+        write(path, "")?;
+    }
+
+    created.insert(1, registry);
+    Ok(created)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::TempDir;
+
+    const REGISTRY: &str = "pub mod day1;
+pub mod day3;
+
+use crate::Solver;
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::of::<day1::Day1>(),
+    Puzzle::of::<day3::Day3>(),
+];
+";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(REGISTRY, 2).unwrap(),
+            "pub mod day1;
+pub mod day2;
+pub mod day3;
+
+use crate::Solver;
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::of::<day1::Day1>(),
+    Puzzle::of::<day2::Day2>(),
+    Puzzle::of::<day3::Day3>(),
+];
+"
+        );
+
+        let registry = register(REGISTRY, 8).unwrap();
+        assert!(registry.contains("pub mod day3;\npub mod day8;\n"));
+        assert!(registry.contains("Day3>(),\n    Puzzle::of::<day8::Day8>(),\n];"));
+        assert!(register(REGISTRY, 3).is_err());
+        assert!(register("pub mod day1;", 2).is_err());
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = TempDir::new("scaffold");
+        fs::create_dir_all(root.join("src").join("days")).unwrap();
+        fs::write(registry_path(&root), REGISTRY).unwrap();
+
+        let written = new_day(&root, 8).unwrap();
+        assert_eq!(written.len(), 7);
+        let module = fs::read_to_string(module_path(&root, 8)).unwrap();
+        assert!(module.contains("pub struct Day8;"));
+        assert!(module.contains("const DAY: u8 = 8;"));
+        assert_eq!(
+            fs::read_to_string(root.join("inputs/day8.txt")).unwrap(),
+            ""
+        );
+        assert!(module.contains("example_tests!(Day8 {"));
+        assert!(module.contains("#[ignore = \"no answer in examples/day8/part2.answer yet\"]"));
+        assert_eq!(crate::examples::list(&root, 8), vec!["part1", "part2"]);
+
+        // never overwrite a day that has been started
+        fs::write(module_path(&root, 8), "// solved").unwrap();
+        assert!(new_day(&root, 8).is_err());
+        assert_eq!(
+            fs::read_to_string(module_path(&root, 8)).unwrap(),
+            "// solved"
+        );
+        assert!(new_day(&root, 26).is_err());
+    }
+}
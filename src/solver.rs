@@ -0,0 +1,39 @@
+use std::time::{Duration, Instant};
+
+// A part's answer, or the error it returned, and how long it took
+pub type Timed = (Result<String, String>, Duration);
+
+// One day's puzzle, split into parsing the input and solving each part so
+// the parsed input can be shared by both parts, timed on its own and tested
+// without reading files.
+pub trait Solver {
+    const DAY: u8;
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, String>;
+    fn part1(input: &Self::Input) -> Result<String, String>;
+    fn part2(input: &Self::Input) -> Result<String, String>;
+
+    // Parses `input` and solves a single part of it
+    fn solve(input: &str, part: u8) -> Result<String, String> {
+        let parsed = Self::parse(input)?;
+        match part {
+            1 => Self::part1(&parsed),
+            2 => Self::part2(&parsed),
+            _ => Err(format!("Day {} has no part {}", Self::DAY, part)),
+        }
+    }
+
+    // Parses `input` once and solves both parts of it, failing as a whole
+    // only if parsing does. Each answer comes with how long it took, the
+    // first including the parse.
+    fn solve_both(input: &str) -> Result<[Timed; 2], String> {
+        let start = Instant::now();
+        let parsed = Self::parse(input)?;
+        let part1 = Self::part1(&parsed);
+        let part1_elapsed = start.elapsed();
+        let start = Instant::now();
+        let part2 = Self::part2(&parsed);
+        Ok([(part1, part1_elapsed), (part2, start.elapsed())])
+    }
+}
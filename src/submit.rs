@@ -0,0 +1,371 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::http::{Client, HttpError, YEAR};
+
+pub const LEDGER: &str = "ledger.tsv";
+
+// What the site said about a submitted answer
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    // wrong, without a hint in either direction
+    Wrong,
+    // submitted too soon after the last attempt, with the wait if given
+    RateLimited(Option<String>),
+    // the site would not take an answer for this part, either because it
+    // is still locked or because it was solved already
+    WrongLevel,
+    // the ledger has a correct answer for the part, never sent by the site
+    AlreadySolved,
+}
+
+impl Outcome {
+    // Reads the outcome out of the page returned after posting an answer.
+    pub fn parse(html: &str) -> Option<Self> {
+        let text = article_text(html);
+        let outcome = if text.contains("That's the right answer") {
+            Outcome::Correct
+        } else if text.contains("answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Outcome::RateLimited(wait)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Outcome::TooHigh
+            } else if text.contains("too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if text.contains("solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            return None;
+        };
+        Some(outcome)
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited(_) => write!(f, "rate-limited"),
+            Outcome::WrongLevel => write!(f, "wrong-level"),
+            Outcome::AlreadySolved => write!(f, "already-solved"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::Wrong),
+            "rate-limited" => Ok(Outcome::RateLimited(None)),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            "already-solved" => Ok(Outcome::AlreadySolved),
+            _ => Err(format!("Unknown outcome {:?}", s)),
+        }
+    }
+}
+
+// The text of the page's <article>, where the site puts its verdict, with
+// tags removed and whitespace collapsed.
+pub fn article_text(html: &str) -> String {
+    // splitting on "<article" leaves us inside the opening tag
+    let (article, mut in_tag) = match html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+    {
+        Some((article, _)) => (article, true),
+        None => (html, false),
+    };
+
+    let mut text = String::new();
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attempt {
+    // seconds since the unix epoch
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.time, self.day, self.part, self.answer, self.outcome
+        )
+    }
+}
+
+impl FromStr for Attempt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split('\t').collect();
+        if fields.len() != 5 {
+            return Err(format!("Expected 5 tab separated fields in {:?}", s));
+        }
+        let number = |field: &str| format!("Invalid number {:?} in {:?}", field, s);
+        Ok(Attempt {
+            time: fields[0].parse().map_err(|_| number(fields[0]))?,
+            day: fields[1].parse().map_err(|_| number(fields[1]))?,
+            part: fields[2].parse().map_err(|_| number(fields[2]))?,
+            answer: fields[3].to_string(),
+            outcome: fields[4].parse()?,
+        })
+    }
+}
+
+// Every answer ever submitted, one tab separated `Attempt` per line. New
+// attempts are appended so the file doubles as a log.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Could not read {}: {}", path.display(), e)),
+        };
+        let attempts = contents
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Ledger { path, attempts })
+    }
+
+    pub fn attempts(&self, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| attempt.day == day && attempt.part == part)
+    }
+
+    // Why `answer` is already known to be wrong, or that the part has been
+    // solved, without asking the site again. Only a correct answer counts as
+    // solved, a wrong level may just mean the part was still locked.
+    pub fn known_outcome(&self, day: u8, part: u8, answer: &str) -> Option<Outcome> {
+        let value = answer.parse::<i128>().ok();
+        for attempt in self.attempts(day, part) {
+            let bound = attempt.answer.parse::<i128>().ok();
+            match (&attempt.outcome, value, bound) {
+                (Outcome::Correct, _, _) => return Some(Outcome::AlreadySolved),
+                (outcome, _, _) if outcome.is_wrong() && attempt.answer == answer => {
+                    return Some(outcome.clone())
+                }
+                (Outcome::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    return Some(Outcome::TooHigh)
+                }
+                (Outcome::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    return Some(Outcome::TooLow)
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", attempt))
+            .map_err(|e| format!("Could not write {}: {}", self.path.display(), e))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Submission {
+    Submitted(Outcome),
+    // not sent, the ledger already says how it would go
+    Skipped(Outcome),
+}
+
+// Posts `answer` unless the ledger shows it is wrong or the part is solved,
+// recording whatever the site replies.
+pub fn submit_answer(
+    client: &Client,
+    ledger: &mut Ledger,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Submission, String> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(format!("Refusing to submit {:?}", answer));
+    }
+    if let Some(outcome) = ledger.known_outcome(day, part, answer) {
+        return Ok(Submission::Skipped(outcome));
+    }
+
+    let html = client
+        .post_form(
+            &format!("/{}/day/{}/answer", YEAR, day),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )
+        .map_err(|err| match err {
+            HttpError::Status(code, body) => format!(
+                "Submitting day {} part {} failed with HTTP {}: {}",
+                day,
+                part,
+                code,
+                article_text(&body)
+            ),
+            HttpError::Other(err) => {
+                format!("Submitting day {} part {} failed: {}", day, part, err)
+            }
+        })?;
+    let outcome = Outcome::parse(&html)
+        .ok_or_else(|| format!("Unrecognised response: {}", article_text(&html)))?;
+
+    ledger.record(Attempt {
+        time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        day,
+        part,
+        answer: answer.to_string(),
+        outcome: outcome.clone(),
+    })?;
+    Ok(Submission::Submitted(outcome))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_outcome_parse() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer."
+            )),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer; your answer is too high."
+            )),
+            Some(Outcome::TooHigh)
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(
+            Outcome::parse(&page("That's not the right answer.")),
+            Some(Outcome::Wrong)
+        );
+        assert_eq!(
+            Outcome::parse(&page(
+                "You gave an answer too recently. You have 4m 12s left to wait."
+            )),
+            Some(Outcome::RateLimited(Some("4m 12s".to_string())))
+        );
+        assert_eq!(
+            Outcome::parse(&page("You don't seem to be solving the right level.")),
+            Some(Outcome::WrongLevel)
+        );
+        assert_eq!(Outcome::parse(&page("Something else")), None);
+    }
+
+    #[test]
+    fn test_known_outcome() {
+        let attempt = |answer: &str, outcome| Attempt {
+            time: 0,
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            outcome,
+        };
+        let mut ledger = Ledger {
+            path: PathBuf::new(),
+            attempts: vec![
+                attempt("500", Outcome::TooHigh),
+                attempt("100", Outcome::TooLow),
+                attempt("abc", Outcome::Wrong),
+                attempt("300", Outcome::RateLimited(None)),
+                attempt("400", Outcome::WrongLevel),
+            ],
+        };
+
+        assert_eq!(ledger.known_outcome(1, 1, "500"), Some(Outcome::TooHigh));
+        assert_eq!(ledger.known_outcome(1, 1, "612"), Some(Outcome::TooHigh));
+        assert_eq!(ledger.known_outcome(1, 1, "99"), Some(Outcome::TooLow));
+        assert_eq!(ledger.known_outcome(1, 1, "abc"), Some(Outcome::Wrong));
+        assert_eq!(ledger.known_outcome(1, 1, "300"), None);
+        assert_eq!(ledger.known_outcome(1, 1, "400"), None);
+        assert_eq!(ledger.known_outcome(1, 2, "500"), None);
+
+        ledger.attempts.push(attempt("250", Outcome::Correct));
+        assert_eq!(
+            ledger.known_outcome(1, 1, "250"),
+            Some(Outcome::AlreadySolved)
+        );
+    }
+
+    #[test]
+    fn test_attempt_round_trip() {
+        let line = "1608800000\t7\t2\t8030\ttoo-low";
+        let attempt: Attempt = line.parse().unwrap();
+        assert_eq!(attempt.outcome, Outcome::TooLow);
+        assert_eq!(attempt.to_string(), line);
+        assert!("1608800000\t7\t2\t8030".parse::<Attempt>().is_err());
+        assert!("1608800000\t7\t2\t8030\tmaybe".parse::<Attempt>().is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+// A fresh scratch directory under the system temp dir for tests, shared by
+// the unit tests and those under tests/. It is removed again when dropped,
+// so a failing assertion does not leave it behind.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("aoc_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_removed_on_drop() {
+        let dir = TempDir::new("testing");
+        let path = dir.to_path_buf();
+        fs::write(dir.join("file.txt"), "x").unwrap();
+        assert!(path.join("file.txt").exists());
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,32 @@
+// Helpers for the small subset of TOML used by the repo's config files:
+// comments, `[table]` headers and `key = value` lines where the value is a
+// string or an integer.
+
+// Drops a trailing `#` comment, leaving any `#` inside a quoted value alone.
+pub(crate) fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..idx],
+            _ => {}
+        }
+    }
+    line
+}
+
+pub(crate) fn parse_value(s: &str, line_no: usize) -> Result<String, String> {
+    if let Some(quoted) = s.strip_prefix('"') {
+        return match quoted.strip_suffix('"') {
+            Some(value) if !value.contains('"') => Ok(value.to_string()),
+            _ => Err(format!("line {}: unterminated string {}", line_no, s)),
+        };
+    }
+    if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return Ok(s.to_string());
+    }
+    Err(format!(
+        "line {}: expected a string or integer, got {:?}",
+        line_no, s
+    ))
+}
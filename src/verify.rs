@@ -0,0 +1,193 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::answers::Answers;
+use crate::days::Puzzle;
+use crate::fetch;
+use crate::json_string;
+use crate::report::{json_ms, json_object};
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass(String),
+    // the solver's answer, or the error it returned instead
+    Fail {
+        expected: String,
+        actual: Result<String, String>,
+    },
+    // no input file or no expected answer to check against
+    Missing(String),
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub elapsed: Duration,
+    pub input_path: PathBuf,
+}
+
+impl Check {
+    // The same fields as a solver's JSON report, plus the status and the
+    // expected answer
+    pub fn to_json(&self) -> String {
+        let mut fields = vec![
+            ("day", self.day.to_string()),
+            ("part", self.part.to_string()),
+        ];
+        match &self.status {
+            Status::Pass(answer) => {
+                fields.push(("status", json_string("pass")));
+                fields.push(("answer", json_string(answer)));
+            }
+            Status::Fail { expected, actual } => {
+                fields.push(("status", json_string("fail")));
+                match actual {
+                    Ok(answer) => fields.push(("answer", json_string(answer))),
+                    Err(err) => fields.push(("error", json_string(err))),
+                }
+                fields.push(("expected", json_string(expected)));
+            }
+            Status::Missing(reason) => {
+                fields.push(("status", json_string("missing")));
+                fields.push(("reason", json_string(reason)));
+            }
+        }
+        fields.push(("elapsed_ms", json_ms(self.elapsed)));
+        fields.push((
+            "input_path",
+            json_string(&self.input_path.display().to_string()),
+        ));
+        json_object(&fields)
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {:>2} part {}  ", self.day, self.part)?;
+        match &self.status {
+            Status::Pass(answer) => write!(f, "pass    {}  ", answer)?,
+            Status::Fail {
+                expected,
+                actual: Ok(actual),
+            } => write!(f, "FAIL    expected {}, got {}  ", expected, actual)?,
+            Status::Fail {
+                expected,
+                actual: Err(err),
+            } => write!(f, "FAIL    expected {}, error: {}  ", expected, err)?,
+            Status::Missing(reason) => return write!(f, "missing {}", reason),
+        }
+        write!(f, "({:.3} ms)", self.elapsed.as_secs_f64() * 1000.0)
+    }
+}
+
+// Checks both parts of a single day against `inputs_dir/dayN.txt`.
+pub fn verify_puzzle(puzzle: &Puzzle, inputs_dir: &Path, answers: &Answers) -> Vec<Check> {
+    let path = fetch::input_path(inputs_dir, puzzle.day);
+    let input = fs::read_to_string(&path);
+
+    (1..=2)
+        .map(|part| {
+            let missing = |reason: String| Check {
+                day: puzzle.day,
+                part,
+                status: Status::Missing(reason),
+                elapsed: Duration::default(),
+                input_path: path.clone(),
+            };
+            let input = match &input {
+                Ok(input) => input,
+                Err(e) => return missing(format!("{}: {}", path.display(), e)),
+            };
+            let expected = match answers.get(puzzle.day, part) {
+                Some(expected) => expected,
+                None => return missing("no expected answer".to_string()),
+            };
+
+            let start = Instant::now();
+            let actual = (puzzle.part(part).unwrap())(input);
+            let elapsed = start.elapsed();
+
+            let status = match actual {
+                Ok(answer) if answer == expected => Status::Pass(answer),
+                actual => Status::Fail {
+                    expected: expected.to_string(),
+                    actual,
+                },
+            };
+            Check {
+                day: puzzle.day,
+                part,
+                status,
+                elapsed,
+                input_path: path.clone(),
+            }
+        })
+        .collect()
+}
+
+pub fn verify(puzzles: &[Puzzle], inputs_dir: &Path, answers: &Answers) -> Vec<Check> {
+    puzzles
+        .iter()
+        .flat_map(|puzzle| verify_puzzle(puzzle, inputs_dir, answers))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::TempDir;
+
+    fn answer(input: &str) -> Result<String, String> {
+        input
+            .trim()
+            .parse::<u32>()
+            .map(|n| n.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    fn double(input: &str) -> Result<String, String> {
+        answer(input).map(|n| (n.parse::<u32>().unwrap() * 2).to_string())
+    }
+
+    #[test]
+    fn test_verify_puzzle() {
+        let dir = TempDir::new("verify");
+        fs::write(dir.join("day1.txt"), "21\n").unwrap();
+
+        let puzzle = Puzzle {
+            day: 1,
+            part1: answer,
+            part2: double,
+            both: |_| Err("not used".to_string()),
+            bench: |_, _| Err("not benchmarked".to_string()),
+        };
+        let mut answers = Answers::default();
+        answers.insert(1, 1, "21");
+        answers.insert(1, 2, "43");
+
+        let checks = verify_puzzle(&puzzle, &dir, &answers);
+        assert_eq!(checks[0].status, Status::Pass("21".to_string()));
+        assert_eq!(
+            checks[1].status,
+            Status::Fail {
+                expected: "43".to_string(),
+                actual: Ok("42".to_string())
+            }
+        );
+        assert!(checks[1]
+            .to_json()
+            .contains("\"status\": \"fail\", \"answer\": \"42\", \"expected\": \"43\""));
+        assert!(checks[1]
+            .to_string()
+            .starts_with("day  1 part 2  FAIL    expected 43, got 42"));
+
+        let missing = Puzzle { day: 2, ..puzzle };
+        answers.insert(2, 1, "1");
+        let checks = verify_puzzle(&missing, &dir, &answers);
+        assert!(matches!(checks[0].status, Status::Missing(_)));
+    }
+}
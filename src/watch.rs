@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::days::Puzzle;
+use crate::examples::{self, examples_dir};
+use crate::json_string;
+use crate::report::json_object;
+
+// What a watched file looked like at one poll, None once it is gone
+type Stamp = Option<(SystemTime, u64)>;
+
+// The modification time and size of every watched file, compared between
+// polls instead of relying on platform specific change notifications.
+#[derive(Debug, Default, PartialEq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, Stamp>,
+}
+
+impl Snapshot {
+    // The input file and everything in the day's examples directory, so a
+    // newly added fixture counts as a change too.
+    pub fn take(root: &Path, input_path: &Path, day: u8) -> Self {
+        let stamp = |path: &Path| {
+            fs::metadata(path)
+                .and_then(|meta| Ok((meta.modified()?, meta.len())))
+                .ok()
+        };
+        let mut files = BTreeMap::new();
+        files.insert(input_path.to_path_buf(), stamp(input_path));
+        let fixtures = fs::read_dir(examples_dir(root, day)).into_iter().flatten();
+        for entry in fixtures.flatten() {
+            let path = entry.path();
+            files.insert(path.clone(), stamp(&path));
+        }
+        Snapshot { files }
+    }
+
+    // Files added, removed or modified since `earlier`
+    pub fn changed<'a>(&'a self, earlier: &'a Snapshot) -> Vec<&'a Path> {
+        let mut paths: Vec<&Path> = self
+            .files
+            .iter()
+            .filter(|(path, stamp)| earlier.files.get(*path) != Some(stamp))
+            .map(|(path, _)| path.as_path())
+            .collect();
+        paths.extend(
+            earlier
+                .files
+                .keys()
+                .filter(|path| !self.files.contains_key(*path))
+                .map(PathBuf::as_path),
+        );
+        paths.sort();
+        paths
+    }
+}
+
+// One answer from a watch run: a part of the puzzle input or an example,
+// with the example's expected answer if it has one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub label: String,
+    pub answer: Result<String, String>,
+    pub expected: Option<String>,
+}
+
+// Parses the input once for both parts, then solves every example for the
+// part it names.
+pub fn run_once(puzzle: &Puzzle, root: &Path, input_path: &Path) -> Vec<Entry> {
+    let entry = |label: String, answer, expected| Entry {
+        label,
+        answer,
+        expected,
+    };
+    let mut entries = vec![];
+    match fs::read_to_string(input_path) {
+        Ok(input) => match (puzzle.both)(&input) {
+            Ok(answers) => {
+                for (part, (answer, _)) in (1..).zip(answers.iter()) {
+                    entries.push(entry(format!("part {}", part), answer.clone(), None));
+                }
+            }
+            Err(err) => entries.push(entry("parse".to_string(), Err(err), None)),
+        },
+        Err(e) => entries.push(entry(
+            "input".to_string(),
+            Err(format!("{}: {}", input_path.display(), e)),
+            None,
+        )),
+    }
+
+    for name in examples::list(root, puzzle.day) {
+        let label = format!("example {}", name);
+        match examples::load(root, puzzle.day, &name) {
+            Ok(example) => {
+                let solve = puzzle.part(example.part).unwrap();
+                entries.push(entry(label, solve(&example.input), example.answer));
+            }
+            Err(err) => entries.push(entry(label, Err(err), None)),
+        }
+    }
+    entries
+}
+
+impl Entry {
+    fn matches(&self) -> Option<bool> {
+        let expected = self.expected.as_ref()?;
+        Some(self.answer.as_ref() == Ok(expected))
+    }
+
+    // e.g. `part 2: 3584591857 (was 3584591856)` or
+    // `example part1: 5 (expected 7)`, comparing against the entry with the
+    // same label from the previous run.
+    pub fn to_human(&self, previous: Option<&Entry>) -> String {
+        let mut line = match &self.answer {
+            Ok(answer) => format!("{}: {}", self.label, answer),
+            Err(err) => format!("{} failed: {}", self.label, err),
+        };
+        match (self.matches(), &self.expected) {
+            (Some(false), Some(expected)) => line += &format!(" (expected {})", expected),
+            (Some(true), _) => line += " (ok)",
+            _ => {}
+        }
+        match previous.map(|previous| &previous.answer) {
+            Some(answer) if *answer == self.answer => line += " (unchanged)",
+            Some(Ok(answer)) => line += &format!(" (was {})", answer),
+            Some(Err(_)) => line += " (was failing)",
+            None => {}
+        }
+        line
+    }
+
+    pub fn to_json(&self, previous: Option<&Entry>) -> String {
+        let result = |answer: &Result<String, String>| match answer {
+            Ok(answer) => ("answer", json_string(answer)),
+            Err(err) => ("error", json_string(err)),
+        };
+        let mut fields = vec![("label", json_string(&self.label)), result(&self.answer)];
+        if let Some(expected) = &self.expected {
+            fields.push(("expected", json_string(expected)));
+        }
+        if let Some(previous) = previous {
+            let (key, value) = result(&previous.answer);
+            let key = if key == "answer" {
+                "previous_answer"
+            } else {
+                "previous_error"
+            };
+            fields.push((key, value));
+        }
+        json_object(&fields)
+    }
+}
+
+pub fn previous<'a>(entries: &'a [Entry], label: &str) -> Option<&'a Entry> {
+    entries.iter().find(|entry| entry.label == label)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::TempDir;
+
+    fn entry(label: &str, answer: Result<&str, &str>, expected: Option<&str>) -> Entry {
+        Entry {
+            label: label.to_string(),
+            answer: answer.map(str::to_string).map_err(str::to_string),
+            expected: expected.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_to_human() {
+        let first = entry("part 1", Ok("211"), None);
+        assert_eq!(first.to_human(None), "part 1: 211");
+        assert_eq!(first.to_human(Some(&first)), "part 1: 211 (unchanged)");
+
+        let second = entry("part 1", Ok("212"), None);
+        assert_eq!(second.to_human(Some(&first)), "part 1: 212 (was 211)");
+        let failing = entry("part 1", Err("no slope"), None);
+        assert_eq!(
+            failing.to_human(Some(&first)),
+            "part 1 failed: no slope (was 211)"
+        );
+        assert_eq!(first.to_human(Some(&failing)), "part 1: 211 (was failing)");
+
+        let example = entry("example part1", Ok("5"), Some("7"));
+        assert_eq!(example.to_human(None), "example part1: 5 (expected 7)");
+        let fixed = entry("example part1", Ok("7"), Some("7"));
+        assert_eq!(
+            fixed.to_human(Some(&example)),
+            "example part1: 7 (ok) (was 5)"
+        );
+        assert_eq!(
+            fixed.to_json(Some(&example)),
+            "{\"label\": \"example part1\", \"answer\": \"7\", \"expected\": \"7\", \"previous_answer\": \"5\"}"
+        );
+    }
+
+    #[test]
+    fn test_run_once() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let puzzle = crate::days::find(1).unwrap();
+        let entries = run_once(puzzle, root, &root.join("inputs/day1.txt"));
+        assert_eq!(entries[0], entry("part 1", Ok("299299"), None));
+        assert_eq!(entries[1], entry("part 2", Ok("287730716"), None));
+        assert_eq!(
+            previous(&entries, "example part1"),
+            Some(&entry("example part1", Ok("514579"), Some("514579")))
+        );
+
+        let entries = run_once(puzzle, root, Path::new("no/such/day1.txt"));
+        assert_eq!(entries[0].label, "input");
+        assert!(entries[0].answer.is_err());
+
+        let dir = TempDir::new("watch");
+        let input = dir.join("day1.txt");
+        fs::write(&input, "one\n").unwrap();
+        let entries = run_once(puzzle, root, &input);
+        assert_eq!(entries[0].label, "parse");
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let root = TempDir::new("snapshot");
+        fs::create_dir_all(examples_dir(&root, 3)).unwrap();
+        let input = root.join("day3.txt");
+        fs::write(&input, "..#\n").unwrap();
+
+        let before = Snapshot::take(&root, &input, 3);
+        assert!(Snapshot::take(&root, &input, 3).changed(&before).is_empty());
+
+        let fixture = examples_dir(&root, 3).join("part1.txt");
+        fs::write(&fixture, "#..\n").unwrap();
+        fs::write(&input, "..#\n#..\n").unwrap();
+        let after = Snapshot::take(&root, &input, 3);
+        assert_eq!(after.changed(&before), vec![input.as_path(), &fixture]);
+
+        fs::remove_file(&fixture).unwrap();
+        let removed = Snapshot::take(&root, &input, 3);
+        assert_eq!(removed.changed(&after), vec![fixture.as_path()]);
+    }
+}
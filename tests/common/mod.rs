@@ -0,0 +1,116 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use aoc_2020::config::Config;
+use aoc_2020::testing::TempDir;
+
+#[derive(Clone, Debug, Default)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+// A local HTTP server standing in for the puzzle site. Every request is
+// recorded and answered by `respond` with a status code and body.
+pub struct StubServer {
+    pub base_url: String,
+    pub requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl StubServer {
+    pub fn start(respond: fn(&Request) -> (u16, String)) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let request = match read_request(&mut BufReader::new(&stream)) {
+                    Some(request) => request,
+                    None => continue,
+                };
+                let (status, body) = respond(&request);
+                recorded.lock().unwrap().push(request);
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        StubServer { base_url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Option<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let mut request = Request {
+        method: parts.next()?.to_string(),
+        path: parts.next()?.to_string(),
+        ..Request::default()
+    };
+
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (key, value) = line.split_once(':')?;
+        request
+            .headers
+            .push((key.trim().to_string(), value.trim().to_string()));
+    }
+
+    let length = request
+        .header("Content-Length")
+        .and_then(|len| len.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+    request.body = String::from_utf8(body).ok()?;
+    Some(request)
+}
+
+// A fresh scratch directory, removed when the test ends or fails
+pub fn temp_dir(name: &str) -> TempDir {
+    TempDir::new(name)
+}
+
+pub fn test_config(server: &StubServer, dir: &std::path::Path) -> Config {
+    Config {
+        session: Some("test-session".to_string()),
+        base_url: server.base_url.clone(),
+        min_interval: std::time::Duration::default(),
+        state_dir: dir.join(".aoc"),
+    }
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use std::fs;
+
+use aoc_2020::fetch::{fetch_input, Fetched};
+use aoc_2020::http::Client;
+
+use common::{temp_dir, test_config, Request, StubServer};
+
+fn respond(request: &Request) -> (u16, String) {
+    match request.path.as_str() {
+        "/2020/day/3/input" => (200, "..#\n#..\n".to_string()),
+        "/2020/day/4/input" => (400, "Puzzle inputs differ by user.".to_string()),
+        _ => (404, "Not Found".to_string()),
+    }
+}
+
+#[test]
+fn test_fetch_downloads_once() {
+    let server = StubServer::start(respond);
+    let dir = temp_dir("fetch_once");
+    let config = test_config(&server, &dir);
+    let client = Client::new(&config);
+    let inputs = dir.join("inputs");
+    let path = inputs.join("day3.txt");
+
+    assert_eq!(
+        fetch_input(&client, 3, &inputs, false),
+        Ok(Fetched::Downloaded(path.clone()))
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "..#\n#..\n");
+    assert_eq!(
+        fetch_input(&client, 3, &inputs, false),
+        Ok(Fetched::Cached(path.clone()))
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].header("Cookie"), Some("session=test-session"));
+
+    assert_eq!(
+        fetch_input(&client, 3, &inputs, true),
+        Ok(Fetched::Downloaded(path))
+    );
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_fetch_errors() {
+    let server = StubServer::start(respond);
+    let dir = temp_dir("fetch_errors");
+    let mut config = test_config(&server, &dir);
+    let inputs = dir.join("inputs");
+
+    let err = fetch_input(&Client::new(&config), 25, &inputs, false).unwrap_err();
+    assert_eq!(err, "Day 25 is not available yet");
+    let err = fetch_input(&Client::new(&config), 4, &inputs, false).unwrap_err();
+    assert!(err.contains("session token"), "{}", err);
+    assert!(!inputs.join("day4.txt").exists());
+
+    config.session = None;
+    assert!(fetch_input(&Client::new(&config), 3, &inputs, false).is_err());
+    assert_eq!(server.requests().len(), 2);
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use aoc_2020::http::Client;
+use aoc_2020::submit::{submit_answer, Ledger, Outcome, Submission};
+
+use common::{temp_dir, test_config, Request, StubServer};
+
+// The mock site: 42 is right, 7 always hits the rate limit, anything else
+// is too high or too low. Part 2 stays locked.
+fn respond(request: &Request) -> (u16, String) {
+    let field = |name: &str| {
+        request
+            .body
+            .split('&')
+            .find_map(|field| field.strip_prefix(name)?.strip_prefix('='))
+            .unwrap_or("")
+    };
+    let text = match field("answer").parse::<u32>() {
+        _ if field("level") == "2" => "You don't seem to be solving the right level.",
+        Ok(42) => "That's the right answer! You are <em>one gold star</em> closer.",
+        Ok(7) => "You gave an answer too recently. You have 58s left to wait.",
+        Ok(n) if n > 42 => "That's not the right answer; your answer is too high.",
+        Ok(_) => "That's not the right answer; your answer is too low.",
+        Err(_) => return (400, "Bad request".to_string()),
+    };
+    (
+        200,
+        format!(
+            "<html><main><article><p>{}</p></article></main></html>",
+            text
+        ),
+    )
+}
+
+#[test]
+fn test_submit_records_attempts() {
+    let server = StubServer::start(respond);
+    let dir = temp_dir("submit_ledger");
+    let config = test_config(&server, &dir);
+    let client = Client::new(&config);
+    let path = dir.join("ledger.tsv");
+    let mut ledger = Ledger::load(&path).unwrap();
+
+    let mut submit = |answer: &str| submit_answer(&client, &mut ledger, 3, 1, answer).unwrap();
+    assert_eq!(submit("100"), Submission::Submitted(Outcome::TooHigh));
+    assert_eq!(submit("100"), Submission::Skipped(Outcome::TooHigh));
+    assert_eq!(submit("150"), Submission::Skipped(Outcome::TooHigh));
+    assert_eq!(
+        submit("7"),
+        Submission::Submitted(Outcome::RateLimited(Some("58s".to_string())))
+    );
+    assert_eq!(
+        submit("7"),
+        Submission::Submitted(Outcome::RateLimited(Some("58s".to_string())))
+    );
+    assert_eq!(submit("42"), Submission::Submitted(Outcome::Correct));
+    assert_eq!(submit("43"), Submission::Skipped(Outcome::AlreadySolved));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2020/day/3/answer");
+    assert_eq!(requests[0].body, "level=1&answer=100");
+    assert_eq!(requests[0].header("Cookie"), Some("session=test-session"));
+
+    // the ledger survives between runs
+    let ledger = Ledger::load(&path).unwrap();
+    let outcomes: Vec<String> = ledger
+        .attempts(3, 1)
+        .map(|attempt| format!("{} {}", attempt.answer, attempt.outcome))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            "100 too-high",
+            "7 rate-limited",
+            "7 rate-limited",
+            "42 correct"
+        ]
+    );
+    assert_eq!(ledger.known_outcome(3, 2, "100"), None);
+}
+
+#[test]
+fn test_submit_errors() {
+    let server = StubServer::start(respond);
+    let dir = temp_dir("submit_errors");
+    let config = test_config(&server, &dir);
+    let client = Client::new(&config);
+    let mut ledger = Ledger::load(dir.join("ledger.tsv")).unwrap();
+
+    assert!(submit_answer(&client, &mut ledger, 3, 1, "").is_err());
+    assert!(submit_answer(&client, &mut ledger, 3, 1, "1 2").is_err());
+    let err = submit_answer(&client, &mut ledger, 3, 1, "abc").unwrap_err();
+    assert!(err.contains("HTTP 400"), "{}", err);
+    assert_eq!(ledger.attempts(3, 1).count(), 0);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_submit_locked_part() {
+    let server = StubServer::start(respond);
+    let dir = temp_dir("submit_locked");
+    let config = test_config(&server, &dir);
+    let client = Client::new(&config);
+    let mut ledger = Ledger::load(dir.join("ledger.tsv")).unwrap();
+
+    // a locked part is recorded but does not count as solved, so the next
+    // attempt still goes to the site
+    let mut submit = |answer: &str| submit_answer(&client, &mut ledger, 3, 2, answer).unwrap();
+    assert_eq!(submit("42"), Submission::Submitted(Outcome::WrongLevel));
+    assert_eq!(submit("42"), Submission::Submitted(Outcome::WrongLevel));
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(ledger.known_outcome(3, 2, "42"), None);
+    assert_eq!(
+        ledger
+            .attempts(3, 2)
+            .map(|a| a.outcome.clone())
+            .collect::<Vec<_>>(),
+        vec![Outcome::WrongLevel, Outcome::WrongLevel]
+    );
+}
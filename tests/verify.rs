@@ -0,0 +1,66 @@
+use std::path::Path;
+
+use aoc_2020::answers::Answers;
+use aoc_2020::days::{Puzzle, PUZZLES};
+use aoc_2020::scaffold::new_day;
+use aoc_2020::testing::TempDir;
+use aoc_2020::verify::{verify, Check, Status};
+
+// Like `aoc verify`, only a wrong answer fails, a day without an input or
+// an expected answer yet is listed but passes.
+fn failures(checks: &[Check]) -> Vec<String> {
+    for check in checks {
+        if let Status::Missing(_) = check.status {
+            eprintln!("{}", check);
+        }
+    }
+    checks
+        .iter()
+        .filter(|check| matches!(check.status, Status::Fail { .. }))
+        .map(|check| check.to_string())
+        .collect()
+}
+
+// Every solver must reproduce the answer recorded for its checked-in input.
+#[test]
+fn test_inputs_match_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let answers = Answers::load(root.join("answers.toml")).unwrap();
+
+    let checks = verify(PUZZLES, &root.join("inputs"), &answers);
+    assert_eq!(checks.len(), PUZZLES.len() * 2);
+    let failures = failures(&checks);
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// A freshly scaffolded day has an empty input and nothing in answers.toml,
+// which must not break the harness.
+#[test]
+fn test_scaffolded_day() {
+    let root = TempDir::new("verify_scaffold");
+    let days = root.join("src").join("days");
+    std::fs::create_dir_all(&days).unwrap();
+    std::fs::write(
+        days.join("mod.rs"),
+        "pub mod day1;\n\npub const PUZZLES: &[Puzzle] = &[\n    Puzzle::of::<day1::Day1>(),\n];\n",
+    )
+    .unwrap();
+    new_day(&root, 8).unwrap();
+    std::fs::write(root.join("answers.toml"), "[day1]\npart1 = \"1\"\n").unwrap();
+    let answers = Answers::load(root.join("answers.toml")).unwrap();
+
+    // what the template's solver does until the day is solved
+    let day8 = Puzzle {
+        day: 8,
+        part1: |_| Err("Day 8 part 1 is not solved yet".to_string()),
+        part2: |_| Err("Day 8 part 2 is not solved yet".to_string()),
+        both: |_| Err("Day 8 is not solved yet".to_string()),
+        bench: |_, _| Err("Day 8 is not solved yet".to_string()),
+    };
+    let checks = verify(&[day8], &root.join("inputs"), &answers);
+    assert_eq!(checks.len(), 2);
+    assert!(checks
+        .iter()
+        .all(|check| matches!(check.status, Status::Missing(_))));
+    assert!(failures(&checks).is_empty());
+}